@@ -116,6 +116,15 @@ static VTTEST_02_15: Lazy<Screen> = Lazy::new(|| {
     parser.screen().clone()
 });
 
+static DENSE_ASCII: Lazy<Screen> = Lazy::new(|| {
+    // Every row filled with same-style ASCII text, the best case for batched cell writes
+    let line = "the quick brown fox jumps over the lazy dog ".repeat(2);
+    let stream = vec![&line[..80]; 24].join("\r\n");
+    let mut parser = vt100::Parser::new(24, 80, 0);
+    parser.process(stream.as_bytes());
+    parser.screen().clone()
+});
+
 #[inline]
 fn render_typescript(screen: &Screen) {
     let backend = TestBackend::new(80, 24);
@@ -134,6 +143,11 @@ fn simple_ls() {
     render_typescript(&SIMPLE_LS_ACTIONS)
 }
 
+#[inline]
+fn dense_ascii() {
+    render_typescript(&DENSE_ASCII)
+}
+
 #[inline]
 fn vttest_02_01() {
     render_typescript(&VTTEST_02_01)
@@ -211,6 +225,7 @@ fn vttest_02_15() {
 
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("simple ls", |b| b.iter(simple_ls));
+    c.bench_function("dense ascii", |b| b.iter(dense_ascii));
     c.bench_function("vttest_02_01", |b| b.iter(vttest_02_01));
     c.bench_function("vttest_02_02", |b| b.iter(vttest_02_02));
     c.bench_function("vttest_02_03", |b| b.iter(vttest_02_03));
@@ -115,6 +115,15 @@ static VTTEST_02_15: Lazy<Screen> = Lazy::new(|| {
     parser.screen().clone()
 });
 
+static DENSE_ASCII: Lazy<Screen> = Lazy::new(|| {
+    // Every row filled with same-style ASCII text, the best case for batched cell writes
+    let line = "the quick brown fox jumps over the lazy dog ".repeat(2);
+    let stream = vec![&line[..80]; 24].join("\r\n");
+    let mut parser = vt100::Parser::new(24, 80, 0);
+    parser.process(stream.as_bytes());
+    parser.screen().clone()
+});
+
 #[inline]
 fn render_typescript(screen: &Screen) {
     let backend = TestBackend::new(80, 24);
@@ -133,6 +142,11 @@ fn simple_ls() {
     render_typescript(&SIMPLE_LS_ACTIONS)
 }
 
+#[inline]
+fn dense_ascii() {
+    render_typescript(&DENSE_ASCII)
+}
+
 #[inline]
 fn vttest_02_01() {
     render_typescript(&VTTEST_02_01)
@@ -210,6 +224,7 @@ fn vttest_02_15() {
 
 iai::main!(
     simple_ls,
+    dense_ascii,
     vttest_02_01,
     vttest_02_02,
     vttest_02_03,
@@ -0,0 +1,284 @@
+//! Export of recorded terminal sessions in the [asciinema v2] `.cast` format.
+//!
+//! [asciinema v2]: https://docs.asciinema.org/manual/asciicast/v2/
+
+use std::{fmt::Write as _, io, time::Duration};
+
+use crate::widget::Screen;
+
+/// The header of a `.cast` file.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::cast::CastHeader;
+///
+/// let parser = vt100::Parser::new(24, 80, 0);
+/// let header = CastHeader::from_screen(parser.screen()).title("demo");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CastHeader {
+    pub(crate) width: u16,
+    pub(crate) height: u16,
+    pub(crate) timestamp: Option<u64>,
+    pub(crate) title: Option<String>,
+}
+
+impl CastHeader {
+    /// Creates a new header for a terminal of the given size.
+    #[inline]
+    #[must_use]
+    pub const fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            timestamp: None,
+            title: None,
+        }
+    }
+
+    /// Creates a new header with the size of the screen.
+    #[inline]
+    #[must_use]
+    pub fn from_screen<S: Screen + ?Sized>(screen: &S) -> Self {
+        let (rows, cols) = screen.size();
+        Self::new(cols, rows)
+    }
+
+    /// Sets the start of the recording, in seconds since the Unix epoch (default = `None`).
+    #[inline]
+    #[must_use]
+    pub const fn timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Sets the title of the recording (default = `None`).
+    #[inline]
+    #[must_use]
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+/// Writes a `.cast` file of the recorded output events.
+///
+/// Every event is the time since the start of the recording and the bytes the program
+/// wrote. Bytes that aren't valid UTF-8 are replaced, as the format only stores text. A
+/// character split across events is written with the event that completes it, an incomplete
+/// character at the end of the recording is replaced in a final event.
+///
+/// # Errors
+///
+/// Returns the errors of the writer.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use tui_term::cast::{write_asciinema, CastHeader};
+///
+/// let events = [(Duration::from_millis(250), b"hello\r\n".to_vec())];
+/// let mut cast = Vec::new();
+/// write_asciinema(&mut cast, &CastHeader::new(80, 24), events).unwrap();
+/// assert!(cast.starts_with(br#"{"version": 2, "width": 80, "height": 24}"#));
+/// ```
+pub fn write_asciinema<W, B>(
+    mut writer: W,
+    header: &CastHeader,
+    events: impl IntoIterator<Item = (Duration, B)>,
+) -> io::Result<()>
+where
+    W: io::Write,
+    B: AsRef<[u8]>,
+{
+    let mut line = format!(
+        r#"{{"version": 2, "width": {}, "height": {}"#,
+        header.width, header.height
+    );
+    if let Some(timestamp) = header.timestamp {
+        let _ = write!(line, r#", "timestamp": {timestamp}"#);
+    }
+    if let Some(title) = &header.title {
+        let _ = write!(line, r#", "title": {}"#, json_string(title));
+    }
+    writeln!(writer, "{line}}}")?;
+    // The start of a character, that the next event completes
+    let mut pending = Vec::new();
+    let mut last_time = Duration::ZERO;
+    for (time, bytes) in events {
+        pending.extend_from_slice(bytes.as_ref());
+        let data = take_utf8(&mut pending);
+        write_event(&mut writer, time, &data)?;
+        last_time = time;
+    }
+    if !pending.is_empty() {
+        write_event(&mut writer, last_time, &String::from_utf8_lossy(&pending))?;
+    }
+    Ok(())
+}
+
+/// Write an output event line.
+fn write_event<W: io::Write>(writer: &mut W, time: Duration, data: &str) -> io::Result<()> {
+    writeln!(
+        writer,
+        r#"[{:.6}, "o", {}]"#,
+        time.as_secs_f64(),
+        json_string(data)
+    )
+}
+
+/// Decode the bytes as UTF-8, replacing invalid sequences, and leave an incomplete character
+/// at the end in `bytes`.
+fn take_utf8(bytes: &mut Vec<u8>) -> String {
+    let mut text = String::with_capacity(bytes.len());
+    let mut rest = &bytes[..];
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                text.push_str(valid);
+                rest = &[];
+                break;
+            }
+            Err(error) => {
+                let (valid, invalid) = rest.split_at(error.valid_up_to());
+                text.push_str(&String::from_utf8_lossy(valid));
+                let Some(len) = error.error_len() else {
+                    rest = invalid;
+                    break;
+                };
+                text.push(char::REPLACEMENT_CHARACTER);
+                rest = &invalid[len..];
+            }
+        }
+    }
+    let keep = rest.len();
+    bytes.drain(..bytes.len() - keep);
+    text
+}
+
+/// Quote the text as a JSON string.
+fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", u32::from(c));
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(all(test, feature = "vt100"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal_cast() {
+        let parser = vt100::Parser::new(24, 80, 0);
+        let header = CastHeader::from_screen(parser.screen())
+            .timestamp(1_700_000_000)
+            .title("a \"demo\"");
+        let events = [
+            (Duration::ZERO, &b"$ ls\r\n"[..]),
+            (
+                Duration::from_micros(1_500_000),
+                &b"\x1b[1msrc\x1b[0m\t\xff"[..],
+            ),
+        ];
+        let mut cast = Vec::new();
+        write_asciinema(&mut cast, &header, events).unwrap();
+
+        let cast = String::from_utf8(cast).unwrap();
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                r#"{"version": 2, "width": 80, "height": 24, "timestamp": 1700000000, "title": "a \"demo\""}"#,
+                r#"[0.000000, "o", "$ ls\r\n"]"#,
+                r#"[1.500000, "o", "\u001b[1msrc\u001b[0m\t�"]"#,
+            ]
+        );
+        assert!(cast.ends_with('\n'));
+    }
+
+    /// Parse an event line into its time, type and data.
+    fn parse_event(line: &str) -> (f64, String, String) {
+        let fields = line.strip_prefix('[').unwrap().strip_suffix(']').unwrap();
+        let (time, fields) = fields.split_once(", ").unwrap();
+        let (kind, data) = fields.split_once(", ").unwrap();
+        (
+            time.parse().unwrap(),
+            parse_string(kind),
+            parse_string(data),
+        )
+    }
+
+    /// Parse a JSON string, as written by `json_string`.
+    fn parse_string(quoted: &str) -> String {
+        let mut chars = quoted
+            .strip_prefix('"')
+            .unwrap()
+            .strip_suffix('"')
+            .unwrap()
+            .chars();
+        let mut text = String::new();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                text.push(c);
+                continue;
+            }
+            match chars.next().unwrap() {
+                'n' => text.push('\n'),
+                'r' => text.push('\r'),
+                't' => text.push('\t'),
+                'u' => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    let code = u32::from_str_radix(&code, 16).unwrap();
+                    text.push(char::from_u32(code).unwrap());
+                }
+                c => text.push(c),
+            }
+        }
+        text
+    }
+
+    #[test]
+    fn characters_split_across_events() {
+        let events = [
+            (Duration::ZERO, &b"h\xc3"[..]),
+            (Duration::from_millis(100), &b"\xa9llo \xf0\x9f"[..]),
+            (Duration::from_millis(200), &b"\x98\x80"[..]),
+            (Duration::from_millis(300), &b"\xff\"!\x1b\xe2"[..]),
+        ];
+        let mut cast = Vec::new();
+        write_asciinema(&mut cast, &CastHeader::new(80, 24), events).unwrap();
+
+        let cast = String::from_utf8(cast).unwrap();
+        let events: Vec<_> = cast.lines().skip(1).map(parse_event).collect();
+        let expected = [
+            (0.0, "h"),
+            (0.1, "éllo "),
+            (0.2, "😀"),
+            (0.3, "\u{fffd}\"!\x1b"),
+            (0.3, "\u{fffd}"),
+        ];
+        assert_eq!(events.len(), expected.len());
+        for ((time, kind, data), (expected_time, expected_data)) in events.iter().zip(expected) {
+            assert!((time - expected_time).abs() < 1e-9);
+            assert_eq!(kind, "o");
+            assert_eq!(data, expected_data);
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Diagnostics for output that doesn't render as expected.
+//!
+//! The `vt100` parser silently drops the escape sequences it doesn't support and doesn't expose
+//! them, so [`SequenceLog`] scans the output itself and reports the sequences `vt100` 0.15 is
+//! known to ignore. Include them when filing a bug about output that renders wrong.
+
+/// The DEC private modes set and reset by `vt100`.
+const DEC_MODES: &[&[u8]] = &[
+    b"1", b"6", b"9", b"25", b"47", b"1000", b"1002", b"1003", b"1005", b"1006", b"1049", b"2004",
+];
+
+/// The longest incomplete sequence kept for the next chunk, in bytes.
+const MAX_PENDING: usize = 4096;
+
+/// Collects the escape sequences of the output, that `vt100` doesn't handle.
+///
+/// The scan is best effort: it knows the sequences handled by `vt100` 0.15, but not whether
+/// their parameters are valid. Sequences split across chunks are reassembled.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::diagnostics::SequenceLog;
+///
+/// let mut log = SequenceLog::default();
+/// log.process(b"\x1b[?2026hsynchronized\x1b[?2026l");
+/// assert_eq!(log.unhandled_sequences().len(), 2);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SequenceLog {
+    unhandled: Vec<Vec<u8>>,
+    pending: Vec<u8>,
+}
+
+impl SequenceLog {
+    /// Scans a chunk of output, e.g. alongside `vt100::Parser::process`.
+    ///
+    /// An escape sequence, which is incomplete at the end of the chunk, is kept until the
+    /// next chunk completes it. A sequence that is still incomplete after 4 KiB, e.g. an
+    /// unterminated OSC string, is reported cut off at that length and the rest of it is
+    /// skipped.
+    pub fn process(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+        let mut rest = &self.pending[..];
+        while let Some(start) = rest.iter().position(|&b| b == 0x1b) {
+            let Some(len) = sequence_len(&rest[start..]) else {
+                break;
+            };
+            let sequence = &rest[start..start + len];
+            if !is_handled(sequence) {
+                self.unhandled.push(sequence.to_vec());
+            }
+            rest = &rest[start + len..];
+        }
+        let keep = rest
+            .iter()
+            .position(|&b| b == 0x1b)
+            .map_or(0, |start| rest.len() - start);
+        self.pending.drain(..self.pending.len() - keep);
+        if self.pending.len() > MAX_PENDING {
+            self.pending.truncate(MAX_PENDING);
+            self.unhandled.push(std::mem::take(&mut self.pending));
+        }
+    }
+
+    /// Returns the unhandled sequences, in the order they were written.
+    #[must_use]
+    pub fn unhandled_sequences(&self) -> Vec<Vec<u8>> {
+        self.unhandled.clone()
+    }
+
+    /// Forgets the sequences collected so far.
+    pub fn clear(&mut self) {
+        self.unhandled.clear();
+    }
+}
+
+/// Returns the length of the escape sequence at the start of `bytes`, if it is complete.
+fn sequence_len(bytes: &[u8]) -> Option<usize> {
+    match *bytes.get(1)? {
+        b'[' => {
+            let end = bytes[2..].iter().position(|b| (0x40..=0x7e).contains(b))?;
+            Some(end + 3)
+        }
+        // OSC, DCS, SOS, PM and APC strings end with BEL or ST
+        b']' | b'P' | b'X' | b'^' | b'_' => {
+            let body = &bytes[2..];
+            body.iter().enumerate().find_map(|(i, &b)| match b {
+                0x07 => Some(i + 3),
+                0x1b => body.get(i + 1).map(|_| i + 4),
+                _ => None,
+            })
+        }
+        _ => {
+            let end = bytes[1..].iter().position(|b| !(0x20..=0x2f).contains(b))?;
+            Some(end + 2)
+        }
+    }
+}
+
+/// Returns whether `vt100` acts on the complete escape `sequence`.
+fn is_handled(sequence: &[u8]) -> bool {
+    let (Some(&kind), Some(&last)) = (sequence.get(1), sequence.last()) else {
+        return false;
+    };
+    match kind {
+        b'[' => {
+            let params = &sequence[2..sequence.len() - 1];
+            if params.iter().any(|b| (0x20..=0x2f).contains(b)) {
+                return false;
+            }
+            match params.first() {
+                Some(b'?') => match last {
+                    b'J' | b'K' => true,
+                    b'h' | b'l' => params[1..]
+                        .split(|&b| b == b';')
+                        .all(|mode| DEC_MODES.contains(&mode)),
+                    _ => false,
+                },
+                Some(b'<' | b'=' | b'>') => false,
+                _ => b"@ABCDEFGHJKLMPSTXdmr".contains(&last),
+            }
+        }
+        b']' => {
+            let code = sequence[2..].split(|&b| b == b';').next();
+            matches!(code, Some(b"0" | b"1" | b"2"))
+        }
+        b'P' | b'X' | b'^' | b'_' => false,
+        _ => sequence.len() == 2 && b"78=>Mcg".contains(&last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_unhandled_sequences() {
+        let mut log = SequenceLog::default();
+        log.process(b"\x1b[1mbold\x1b[0m \x1b[?2026h\x1b]0;title\x07\x1b]52;c;Zm9v");
+        log.process(b"\x07\x1b[?25l\x1b[4 q\x1b#8\x1b[");
+        log.process(b"5ndone");
+
+        assert_eq!(
+            log.unhandled_sequences(),
+            [
+                b"\x1b[?2026h".to_vec(),
+                b"\x1b]52;c;Zm9v\x07".to_vec(),
+                b"\x1b[4 q".to_vec(),
+                b"\x1b#8".to_vec(),
+                b"\x1b[5n".to_vec(),
+            ]
+        );
+    }
+
+    #[test]
+    fn unterminated_sequence_is_cut_off() {
+        let mut log = SequenceLog::default();
+        log.process(b"\x1b]52;c;");
+        for _ in 0..1000 {
+            log.process(&[b'A'; 100]);
+            assert!(log.pending.len() <= MAX_PENDING);
+        }
+        log.process(b"\x07\x1b[?2026h");
+
+        let unhandled = log.unhandled_sequences();
+        assert_eq!(unhandled.len(), 2);
+        assert_eq!(unhandled[0].len(), MAX_PENDING);
+        assert!(unhandled[0].starts_with(b"\x1b]52;c;AAAA"));
+        assert_eq!(unhandled[1], b"\x1b[?2026h");
+    }
+}
@@ -0,0 +1,610 @@
+//! Plain text export of a [`Screen`].
+
+use std::ops::Range;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier},
+    text::Span,
+};
+
+use crate::widget::{cell_width, Cell, Charset, Screen};
+
+/// The symbol that marks the cursor position in exported text.
+pub const CURSOR_MARKER: char = '\u{2588}'; //"█".
+
+/// Options controlling how the contents of a screen are exported as text.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::{export::TextExportOptions, widget::PseudoTerminal};
+///
+/// let mut parser = vt100::Parser::new(24, 80, 0);
+/// parser.process(b"hello");
+/// let options = TextExportOptions::default().pad_to_width(true);
+/// let text = PseudoTerminal::new(parser.screen()).to_plain_text_with(&options);
+/// assert_eq!(text.lines().next().unwrap().len(), 80);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextExportOptions {
+    pub(crate) trim_trailing: bool,
+    pub(crate) include_cursor_marker: bool,
+    pub(crate) pad_to_width: bool,
+    pub(crate) linedraw: LinedrawMode,
+}
+
+impl TextExportOptions {
+    /// Trim trailing whitespace of every line and trailing empty lines (default = true).
+    #[inline]
+    #[must_use]
+    pub const fn trim_trailing(mut self, trim_trailing: bool) -> Self {
+        self.trim_trailing = trim_trailing;
+        self
+    }
+
+    /// Replace the cell under the cursor with [`CURSOR_MARKER`] (default = false).
+    ///
+    /// A wide character under the cursor is replaced by a single marker.
+    #[inline]
+    #[must_use]
+    pub const fn include_cursor_marker(mut self, include_cursor_marker: bool) -> Self {
+        self.include_cursor_marker = include_cursor_marker;
+        self
+    }
+
+    /// Pad every line with spaces to the width of the screen (default = false).
+    ///
+    /// This produces fixed-width output, which is useful for diffing.
+    /// Lines are padded even if trailing whitespace is trimmed.
+    #[inline]
+    #[must_use]
+    pub const fn pad_to_width(mut self, pad_to_width: bool) -> Self {
+        self.pad_to_width = pad_to_width;
+        self
+    }
+
+    /// Sets how line drawing cells of the DEC special graphics character set are exported
+    /// (default = [`LinedrawMode::Raw`]).
+    ///
+    /// This matters when copying e.g. `tree` or `tput` output into documents.
+    /// Only cells that report [`Charset::DecSpecialGraphics`] are translated, which the
+    /// `vt100` backend never does, see [`Cell::charset`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::export::{LinedrawMode, TextExportOptions};
+    ///
+    /// let options = TextExportOptions::default().linedraw(LinedrawMode::Unicode);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn linedraw(mut self, linedraw: LinedrawMode) -> Self {
+        self.linedraw = linedraw;
+        self
+    }
+
+    /// Export line drawing cells of the DEC special graphics character set as ASCII
+    /// approximations (default = false).
+    ///
+    /// This is a shorthand for [`LinedrawMode::Ascii`], disabling it exports the cells
+    /// [raw](LinedrawMode::Raw).
+    #[inline]
+    #[must_use]
+    pub const fn ascii_linedraw(mut self, ascii_linedraw: bool) -> Self {
+        self.linedraw = if ascii_linedraw {
+            LinedrawMode::Ascii
+        } else {
+            LinedrawMode::Raw
+        };
+        self
+    }
+}
+
+impl Default for TextExportOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            trim_trailing: true,
+            include_cursor_marker: false,
+            pad_to_width: false,
+            linedraw: LinedrawMode::Raw,
+        }
+    }
+}
+
+/// How line drawing cells are exported, see [`TextExportOptions::linedraw`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinedrawMode {
+    /// Export the contents as the screen stores them
+    #[default]
+    Raw,
+    /// Export the raw DEC special graphics letters as their Unicode equivalents, e.g. `q` as
+    /// `─`
+    Unicode,
+    /// Export corners and junctions as `+`, horizontal lines as `-` and vertical lines as `|`
+    Ascii,
+}
+
+/// Export the contents of the [`Screen`] as text, with one line per row.
+///
+/// With a `tab_width`, runs of at least two blank cells, that end at a tab stop, are exported
+/// as a tab.
+pub(crate) fn plain_text<S: Screen + ?Sized>(
+    screen: &S,
+    options: &TextExportOptions,
+    tab_width: Option<u16>,
+) -> String {
+    let (rows, cols) = screen.size();
+    let cursor = options
+        .include_cursor_marker
+        .then(|| screen.cursor_position());
+
+    let mut lines = Vec::with_capacity(rows.into());
+    for row in 0..rows {
+        let mut line = String::new();
+        // Blank cells, that may still turn into a tab
+        let mut blanks = 0;
+        let mut col = 0;
+        while col < cols {
+            let cell = screen.cell(row, col);
+            let (text, width) = if cursor == Some((row, col)) {
+                // A wide character is replaced as a whole, without its continuation cell
+                (CURSOR_MARKER.to_string(), cell_columns(cell))
+            } else {
+                (cell_text(cell, options), cell_columns(cell))
+            };
+            if tab_width.is_some() && text == " " {
+                blanks += 1;
+            } else {
+                line.push_str(&" ".repeat(blanks));
+                blanks = 0;
+                line.push_str(&text);
+            }
+            col += width;
+            if blanks > 0 && tab_width.and_then(|tab_width| col.checked_rem(tab_width)) == Some(0) {
+                // A single blank is more likely a space than a tab
+                if blanks == 1 {
+                    line.push(' ');
+                } else {
+                    line.push('\t');
+                }
+                blanks = 0;
+            }
+        }
+        line.push_str(&" ".repeat(blanks));
+        if options.trim_trailing && !options.pad_to_width {
+            line.truncate(line.trim_end().len());
+        }
+        lines.push(line);
+    }
+    if options.trim_trailing {
+        while lines.last().is_some_and(|line| line.trim_end().is_empty()) {
+            lines.pop();
+        }
+    }
+    lines.join("\n")
+}
+
+/// Split the rows of the [`Screen`] into the columns between the given bounds.
+///
+/// Every column is trimmed, trailing blank rows are skipped.
+pub(crate) fn table_cells<S: Screen + ?Sized>(screen: &S, bounds: &[u16]) -> Vec<Vec<String>> {
+    let (rows, _) = screen.size();
+    let mut table: Vec<Vec<String>> = (0..rows)
+        .map(|row| {
+            bounds
+                .windows(2)
+                .map(|bound| row_text(screen, row, bound[0]..bound[1]).trim().to_string())
+                .collect()
+        })
+        .collect();
+    while table
+        .last()
+        .is_some_and(|row| row.iter().all(String::is_empty))
+    {
+        table.pop();
+    }
+    table
+}
+
+/// The sorted bounds of the table columns, from the first to past the last screen column.
+///
+/// Separators outside of the screen are ignored.
+pub(crate) fn column_bounds(cols: u16, column_seps: &[u16]) -> Vec<u16> {
+    let mut bounds = vec![0, cols];
+    bounds.extend(column_seps.iter().filter(|&&sep| sep > 0 && sep < cols));
+    bounds.sort_unstable();
+    bounds.dedup();
+    bounds
+}
+
+/// The text of a single cell, according to the export options.
+fn cell_text<C: Cell + ?Sized>(cell: Option<&C>, options: &TextExportOptions) -> String {
+    let symbol = cell_symbol(cell);
+    if !cell.is_some_and(|cell| cell.charset() == Charset::DecSpecialGraphics) {
+        return symbol;
+    }
+    let replacement = match options.linedraw {
+        LinedrawMode::Raw => None,
+        LinedrawMode::Unicode => linedraw_to_unicode(&symbol),
+        LinedrawMode::Ascii => linedraw_to_ascii(&symbol),
+    };
+    replacement.map_or(symbol, String::from)
+}
+
+/// Unicode equivalent of a DEC special graphics letter.
+fn linedraw_to_unicode(symbol: &str) -> Option<char> {
+    const GLYPHS: [char; 31] = [
+        '◆', '▒', '␉', '␌', '␍', '␊', '°', '±', '␤', '␋', '┘', '┐', '┌', '└', '┼', '⎺', '⎻', '─',
+        '⎼', '⎽', '├', '┤', '┴', '┬', '│', '≤', '≥', 'π', '≠', '£', '·',
+    ];
+    let mut chars = symbol.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c @ '`'..='~'), None) => Some(GLYPHS[c as usize - '`' as usize]),
+        _ => None,
+    }
+}
+
+/// ASCII approximation of a line drawing glyph.
+///
+/// Both the raw DEC special graphics letters and their Unicode box drawing equivalents
+/// are recognized.
+fn linedraw_to_ascii(symbol: &str) -> Option<char> {
+    match symbol {
+        "j" | "k" | "l" | "m" | "n" | "t" | "u" | "v" | "w" => Some('+'),
+        "┘" | "┐" | "┌" | "└" | "┼" | "├" | "┤" | "┴" | "┬" => Some('+'),
+        "q" | "─" => Some('-'),
+        "x" | "│" => Some('|'),
+        _ => None,
+    }
+}
+
+/// The symbol a cell renders as, blank cells render as a space.
+fn cell_symbol<C: Cell + ?Sized>(cell: Option<&C>) -> String {
+    match cell.map(Cell::contents) {
+        Some(contents) if !contents.is_empty() => contents,
+        _ => " ".to_string(),
+    }
+}
+
+/// The text of the columns of a row, skipping the continuation cells of wide characters.
+fn row_text<S: Screen + ?Sized>(screen: &S, row: u16, cols: Range<u16>) -> String {
+    let mut text = String::new();
+    let mut col = cols.start;
+    while col < cols.end {
+        let cell = screen.cell(row, col);
+        text.push_str(&cell_symbol(cell));
+        col += cell_columns(cell);
+    }
+    text
+}
+
+/// The styled spans of a row, adjacent cells with the same style are merged into one span.
+pub(crate) fn row_spans<S: Screen + ?Sized>(screen: &S, row: u16) -> Vec<Span<'static>> {
+    let (rows, cols) = screen.size();
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    if row >= rows {
+        return spans;
+    }
+    let mut col = 0;
+    while col < cols {
+        let cell = screen.cell(row, col);
+        let mut scratch = ratatui::buffer::Cell::default();
+        if let Some(cell) = cell {
+            cell.apply(&mut scratch);
+        }
+        let style = scratch.style();
+        match spans.last_mut() {
+            Some(span) if span.style == style => span.content.to_mut().push_str(scratch.symbol()),
+            _ => spans.push(Span::styled(scratch.symbol().to_string(), style)),
+        }
+        col += cell_columns(cell);
+    }
+    spans
+}
+
+/// The columns and text of the word at (row, column), `None` if the cell is blank.
+///
+/// A word is a run of cells, that aren't whitespace, within the row.
+pub(crate) fn word_at<S: Screen + ?Sized>(
+    screen: &S,
+    row: u16,
+    col: u16,
+) -> Option<(Range<u16>, String)> {
+    let cols = screen.size().1;
+    // Continuation cells of wide characters belong to the character before them
+    let is_word = |col: u16| {
+        let contents = screen
+            .cell(row, col)
+            .map(Cell::contents)
+            .unwrap_or_default();
+        if contents.is_empty() {
+            col.checked_sub(1).is_some_and(|prev| {
+                screen
+                    .cell(row, prev)
+                    .is_some_and(|cell| cell_width(&cell.contents()) > 1)
+            })
+        } else {
+            !contents.trim().is_empty()
+        }
+    };
+    if col >= cols || !is_word(col) {
+        return None;
+    }
+    let mut start = col;
+    while start > 0 && is_word(start - 1) {
+        start -= 1;
+    }
+    let mut end = col + 1;
+    while end < cols && is_word(end) {
+        end += 1;
+    }
+    let text = (start..end)
+        .filter_map(|col| screen.cell(row, col).map(Cell::contents))
+        .collect();
+    Some((start..end, text))
+}
+
+/// Join the rows of every logical line and wrap them at word boundaries to `width` columns.
+///
+/// Words longer than the width are broken, trailing empty lines are skipped.
+pub(crate) fn reflow<S: Screen + ?Sized>(screen: &S, width: u16) -> String {
+    let (rows, cols) = screen.size();
+    let mut logical_lines = Vec::new();
+    let mut line = String::new();
+    for row in 0..rows {
+        line.push_str(&row_text(screen, row, 0..cols));
+        if !screen.row_wrapped(row) {
+            logical_lines.push(std::mem::take(&mut line));
+        }
+    }
+    if !line.is_empty() {
+        logical_lines.push(line);
+    }
+    while logical_lines
+        .last()
+        .is_some_and(|line| line.trim_end().is_empty())
+    {
+        logical_lines.pop();
+    }
+
+    let mut lines = Vec::new();
+    for line in logical_lines {
+        wrap_words(&line, width.max(1), &mut lines);
+    }
+    lines.join("\n")
+}
+
+/// Wrap the words of the line to `width` columns, breaking words that are longer.
+fn wrap_words(line: &str, width: u16, lines: &mut Vec<String>) {
+    let mut current = String::new();
+    let mut current_width: u16 = 0;
+    for word in line.split_whitespace() {
+        let word_width = cell_width(word);
+        if current_width > 0 && current_width.saturating_add(word_width) >= width {
+            lines.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+        if current_width > 0 {
+            current.push(' ');
+            current_width += 1;
+        }
+        for c in word.chars() {
+            let char_width = cell_width(c.encode_utf8(&mut [0; 4]));
+            if current_width > 0 && current_width.saturating_add(char_width) > width {
+                lines.push(std::mem::take(&mut current));
+                current_width = 0;
+            }
+            current.push(c);
+            current_width = current_width.saturating_add(char_width);
+        }
+    }
+    lines.push(current);
+}
+
+/// The number of columns a cell covers, a wide character covers its continuation cell.
+fn cell_columns<C: Cell + ?Sized>(cell: Option<&C>) -> u16 {
+    cell_width(&cell_symbol(cell)).max(1)
+}
+
+/// Downsample the screen into Braille patterns, that fit into `width` x `height` cells.
+///
+/// Every Braille cell has 2x4 dots, a dot is raised if any cell of the block of the screen it
+/// covers is not blank. The rows are stretched by the `aspect` of the cells first.
+pub(crate) fn braille<S: Screen + ?Sized>(
+    screen: &S,
+    width: u16,
+    height: u16,
+    aspect: f32,
+) -> Vec<String> {
+    // The bits of the dots, indexed by [row][column] of the dot
+    const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+    let (screen_rows, cols) = screen.size();
+    if width == 0 || height == 0 || screen_rows == 0 {
+        return Vec::new();
+    }
+    // Every screen row is sampled as `aspect` rows, so that the cells come out square
+    let rows = ((f32::from(screen_rows) * aspect).round() as u16).max(1);
+    let screen_row = |row: u32| (row * u32::from(screen_rows) / u32::from(rows)) as u16;
+    let col_step = cols.div_ceil(width.saturating_mul(2)).max(1);
+    let row_step = rows.div_ceil(height.saturating_mul(4)).max(1);
+    // The blocks of the last dots may end past `u16::MAX`, so they are computed in `u32`
+    let filled = |dot_row: u32, dot_col: u32| {
+        let (row_step, col_step) = (u32::from(row_step), u32::from(col_step));
+        let rows = dot_row * row_step..((dot_row + 1) * row_step).min(rows.into());
+        rows.into_iter().any(|row| {
+            let cols = dot_col * col_step..((dot_col + 1) * col_step).min(cols.into());
+            cols.into_iter().any(|col| {
+                !cell_symbol(screen.cell(screen_row(row), col as u16))
+                    .trim()
+                    .is_empty()
+            })
+        })
+    };
+
+    let dot_rows = rows.div_ceil(row_step);
+    let dot_cols = cols.div_ceil(col_step);
+    (0..dot_rows.div_ceil(4))
+        .map(|y| {
+            (0..dot_cols.div_ceil(2))
+                .map(|x| {
+                    let mut pattern = 0;
+                    for (dy, bits) in (0..).zip(DOTS) {
+                        for (dx, bit) in (0..).zip(bits) {
+                            if filled(u32::from(y) * 4 + dy, u32::from(x) * 2 + dx) {
+                                pattern |= bit;
+                            }
+                        }
+                    }
+                    char::from_u32(0x2800 + pattern).unwrap_or(' ')
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Find the `http://` and `https://` URLs in the text of the screen.
+///
+/// Rows that wrap into the next row are joined, so a URL can span several rows,
+/// it is returned once for every row it covers.
+pub(crate) fn detect_urls<S: Screen + ?Sized>(screen: &S) -> Vec<(Rect, String)> {
+    let (rows, cols) = screen.size();
+    let mut urls = Vec::new();
+    let mut row = 0;
+    while row < rows {
+        // The (row, column) of every character of the line
+        let mut positions = Vec::new();
+        let mut text = String::new();
+        loop {
+            for col in 0..cols {
+                let symbol = cell_symbol(screen.cell(row, col));
+                // Only ASCII is matched, so byte offsets into the text are cell offsets
+                let mut chars = symbol.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if c.is_ascii() => text.push(c),
+                    _ => text.push(' '),
+                }
+                positions.push((row, col));
+            }
+            row += 1;
+            if row >= rows || !screen.row_wrapped(row - 1) {
+                break;
+            }
+        }
+        find_urls(&text, &positions, &mut urls);
+    }
+    urls
+}
+
+fn find_urls(text: &str, positions: &[(u16, u16)], urls: &mut Vec<(Rect, String)>) {
+    let mut offset = 0;
+    while let Some(found) = text[offset..].find("http") {
+        let start = offset + found;
+        let rest = &text[start..];
+        let len = rest
+            .find(|c: char| c.is_ascii_whitespace() || matches!(c, '"' | '\'' | '<' | '>' | '`'))
+            .unwrap_or(rest.len());
+        offset = start + len.max(1);
+        // Punctuation at the end most likely belongs to the surrounding text
+        let url = rest[..len].trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']']);
+        let scheme_len = ["https://", "http://"]
+            .into_iter()
+            .find(|scheme| url.starts_with(scheme))
+            .map_or(usize::MAX, str::len);
+        if url.len() <= scheme_len {
+            continue;
+        }
+        // One rectangle for every row the URL covers
+        let mut cells = positions[start..start + url.len()].iter().peekable();
+        while let Some(&(row, col)) = cells.next() {
+            let mut width = 1;
+            while cells.next_if(|(next_row, _)| *next_row == row).is_some() {
+                width += 1;
+            }
+            urls.push((Rect::new(col, row, width, 1), url.to_string()));
+        }
+    }
+}
+
+/// Names of the modifiers in the debug rendering, in a fixed order.
+const MODIFIER_NAMES: &[(Modifier, &str)] = &[
+    (Modifier::BOLD, "BOLD"),
+    (Modifier::DIM, "DIM"),
+    (Modifier::ITALIC, "ITALIC"),
+    (Modifier::UNDERLINED, "UNDERLINED"),
+    (Modifier::SLOW_BLINK, "SLOW_BLINK"),
+    (Modifier::RAPID_BLINK, "RAPID_BLINK"),
+    (Modifier::REVERSED, "REVERSED"),
+    (Modifier::HIDDEN, "HIDDEN"),
+    (Modifier::CROSSED_OUT, "CROSSED_OUT"),
+];
+
+/// Format every cell of the buffer as `symbol+fg+bg+modifiers`, with one line per row.
+///
+/// Cells are separated by a space, modifiers by `|`, a cell without modifiers shows `-`.
+/// The format only depends on the cells, so it is stable across platforms.
+pub(crate) fn debug_string(buf: &Buffer) -> String {
+    let area = buf.area;
+    let mut lines = Vec::with_capacity(area.height.into());
+    for y in area.top()..area.bottom() {
+        let cells: Vec<String> = (area.left()..area.right())
+            .map(|x| {
+                let cell = &buf[(x, y)];
+                let modifiers: Vec<&str> = MODIFIER_NAMES
+                    .iter()
+                    .filter(|(modifier, _)| cell.modifier.contains(*modifier))
+                    .map(|(_, name)| *name)
+                    .collect();
+                let modifiers = if modifiers.is_empty() {
+                    "-".to_string()
+                } else {
+                    modifiers.join("|")
+                };
+                format!(
+                    "{}+{}+{}+{}",
+                    cell.symbol(),
+                    color_name(cell.fg),
+                    color_name(cell.bg),
+                    modifiers
+                )
+            })
+            .collect();
+        lines.push(cells.join(" "));
+    }
+    lines.join("\n")
+}
+
+/// A 64-bit FNV-1a hash of the text and styles of every cell of the screen.
+///
+/// The hash is taken over the [`debug_string`] of the cells, so it is stable across
+/// platforms and releases of the standard library.
+pub(crate) fn content_digest<S: Screen + ?Sized>(screen: &S) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let (rows, cols) = screen.size();
+    let mut buf = Buffer::empty(Rect::new(0, 0, cols, rows));
+    for row in 0..rows {
+        for col in 0..cols {
+            if let Some(cell) = screen.cell(row, col) {
+                cell.apply(&mut buf[(col, row)]);
+            }
+        }
+    }
+    debug_string(&buf).bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// A name of the color without whitespace, RGB colors are formatted as `#rrggbb`.
+fn color_name(color: Color) -> String {
+    match color {
+        Color::Rgb(r, g, b) => format!("#{r:02x}{g:02x}{b:02x}"),
+        Color::Indexed(i) => format!("Indexed{i}"),
+        color => format!("{color:?}"),
+    }
+}
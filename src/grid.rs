@@ -0,0 +1,165 @@
+//! Rendering of multiple screens side by side.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Widget},
+};
+
+use crate::widget::{Cursor, PseudoTerminal, Screen};
+
+/// A widget rendering multiple screens in a grid, e.g. for dashboards or multiplexers.
+///
+/// Every screen is rendered as a [`PseudoTerminal`] in its own tile, sharing the block,
+/// cursor and style configuration.
+/// The tiles are laid out row by row, with the given number of columns.
+///
+/// # Example
+///
+/// ```rust
+/// use ratatui::widgets::{Block, Borders};
+/// use tui_term::grid::TerminalGrid;
+///
+/// let parsers = [vt100::Parser::new(24, 80, 0), vt100::Parser::new(24, 80, 0)];
+/// let screens: Vec<_> = parsers.iter().map(vt100::Parser::screen).collect();
+/// let grid = TerminalGrid::new(&screens, 2).block(Block::default().borders(Borders::ALL));
+/// ```
+pub struct TerminalGrid<'a, S: ?Sized> {
+    screens: &'a [&'a S],
+    columns: u16,
+    block: Option<Block<'a>>,
+    style: Option<Style>,
+    cursor: Cursor,
+}
+
+impl<'a, S: Screen + ?Sized> TerminalGrid<'a, S> {
+    /// Creates a new instance of `TerminalGrid`.
+    ///
+    /// # Arguments
+    ///
+    /// * `screens`: The screens to render, in row-major order.
+    /// * `columns`: The number of tiles per row, at least one.
+    #[inline]
+    #[must_use]
+    pub fn new(screens: &'a [&'a S], columns: u16) -> Self {
+        Self {
+            screens,
+            columns: columns.max(1),
+            block: None,
+            style: None,
+            cursor: Cursor::default(),
+        }
+    }
+
+    /// Sets the block that surrounds every tile.
+    #[inline]
+    #[must_use]
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Sets the cursor configuration of every tile.
+    #[inline]
+    #[must_use]
+    pub fn cursor(mut self, cursor: Cursor) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// Sets the style of every tile.
+    #[inline]
+    #[must_use]
+    pub const fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// The areas of the tiles, in the order of the screens.
+    ///
+    /// The last row and column absorb the remainder of the area.
+    #[must_use]
+    pub fn tiles(&self, area: Rect) -> Vec<Rect> {
+        let count = u16::try_from(self.screens.len()).unwrap_or(u16::MAX);
+        let columns = self.columns.min(count).max(1);
+        let rows = count.div_ceil(columns).max(1);
+        let width = area.width / columns;
+        let height = area.height / rows;
+
+        (0..count)
+            .map(|index| {
+                let (row, col) = (index / columns, index % columns);
+                let x = area.x + col * width;
+                let y = area.y + row * height;
+                let width = if col + 1 == columns {
+                    area.right() - x
+                } else {
+                    width
+                };
+                let height = if row + 1 == rows {
+                    area.bottom() - y
+                } else {
+                    height
+                };
+                Rect::new(x, y, width, height)
+            })
+            .collect()
+    }
+}
+
+impl<S: Screen + ?Sized> Widget for TerminalGrid<'_, S> {
+    #[inline]
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for (screen, tile) in self.screens.iter().zip(self.tiles(area)) {
+            let mut pseudo_term = PseudoTerminal::new(*screen).cursor(self.cursor.clone());
+            if let Some(block) = &self.block {
+                pseudo_term = pseudo_term.block(block.clone());
+            }
+            if let Some(style) = self.style {
+                pseudo_term = pseudo_term.style(style);
+            }
+            pseudo_term.render(tile, buf);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "vt100"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_by_two_grid() {
+        let parsers: Vec<_> = ["one", "two", "three", "four"]
+            .iter()
+            .map(|text| {
+                let mut parser = vt100::Parser::new(5, 10, 0);
+                parser.process(text.as_bytes());
+                parser
+            })
+            .collect();
+        let screens: Vec<_> = parsers.iter().map(vt100::Parser::screen).collect();
+        let grid = TerminalGrid::new(&screens, 2).cursor(Cursor::default().visibility(false));
+
+        let area = Rect::new(0, 0, 20, 10);
+        assert_eq!(
+            grid.tiles(area),
+            vec![
+                Rect::new(0, 0, 10, 5),
+                Rect::new(10, 0, 10, 5),
+                Rect::new(0, 5, 10, 5),
+                Rect::new(10, 5, 10, 5),
+            ]
+        );
+
+        let mut buf = Buffer::empty(area);
+        grid.render(area, &mut buf);
+        let row_text = |x: u16, y: u16, len: u16| -> String {
+            (x..x + len).map(|x| buf[(x, y)].symbol()).collect()
+        };
+        assert_eq!(row_text(0, 0, 3), "one");
+        assert_eq!(row_text(10, 0, 3), "two");
+        assert_eq!(row_text(0, 5, 5), "three");
+        assert_eq!(row_text(10, 5, 4), "four");
+    }
+}
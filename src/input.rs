@@ -0,0 +1,1173 @@
+//! Translation of host input into the byte sequences a program in the terminal expects.
+//!
+//! Enable the `crossterm` feature for conversions from `crossterm` events.
+
+use ratatui::layout::{Position, Rect};
+
+/// Sequence sent to the program when the host window gains focus.
+pub const FOCUS_IN: &[u8] = b"\x1b[I";
+/// Sequence sent to the program when the host window loses focus.
+pub const FOCUS_OUT: &[u8] = b"\x1b[O";
+
+/// Returns the sequence reporting a focus change of the host window.
+///
+/// `enabled` is whether the program enabled focus reporting,
+/// see [`Screen::focus_reporting`](crate::widget::Screen::focus_reporting).
+/// Returns `None` if focus reporting is disabled, as the program doesn't expect the sequence.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::input::focus_event;
+///
+/// assert_eq!(focus_event(true, true), Some(b"\x1b[I".to_vec()));
+/// assert_eq!(focus_event(false, false), None);
+/// ```
+#[inline]
+#[must_use]
+pub fn focus_event(gained: bool, enabled: bool) -> Option<Vec<u8>> {
+    if !enabled {
+        return None;
+    }
+    let sequence = if gained { FOCUS_IN } else { FOCUS_OUT };
+    Some(sequence.to_vec())
+}
+
+/// Sequence of the up arrow key, sent for every line scrolled up in alternate scroll mode.
+pub const ARROW_UP: &[u8] = b"\x1b[A";
+/// Sequence of the down arrow key, sent for every line scrolled down in alternate scroll mode.
+pub const ARROW_DOWN: &[u8] = b"\x1b[B";
+
+/// Returns the arrow keys a mouse wheel scroll of `lines` translates to.
+///
+/// `enabled` is whether the alternate screen is active and the program enabled alternate
+/// scroll mode, see [`Screen::alt_scroll`](crate::widget::Screen::alt_scroll), which the
+/// `vt100` backend doesn't report.
+/// Returns `None` if it is disabled, the scroll should then move the scrollback instead.
+/// This makes pagers like `less` scrollable with the wheel.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::input::alt_scroll_keys;
+///
+/// assert_eq!(
+///     alt_scroll_keys(true, 2, true),
+///     Some(b"\x1b[A\x1b[A".to_vec())
+/// );
+/// assert_eq!(alt_scroll_keys(true, 2, false), None);
+/// ```
+#[inline]
+#[must_use]
+pub fn alt_scroll_keys(up: bool, lines: u16, enabled: bool) -> Option<Vec<u8>> {
+    if !enabled {
+        return None;
+    }
+    let key = if up { ARROW_UP } else { ARROW_DOWN };
+    Some(key.repeat(lines.into()))
+}
+
+/// Primary device attributes (DA1) reply: a VT220 with ANSI color support.
+pub const DEVICE_ATTRIBUTES: &[u8] = b"\x1b[?62;22c";
+
+/// Returns the reply to a primary device attributes query (`\x1b[c`).
+///
+/// Programs send the query to detect the capabilities of the terminal and may wait for
+/// the reply, which should be written back to the program.
+/// `vt100` doesn't report queries, use [`contains_device_attributes_request`] on the output
+/// of the program to detect them.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::input::{contains_device_attributes_request, device_attributes_response};
+///
+/// let output = b"\x1b[c";
+/// if contains_device_attributes_request(output) {
+///     let reply = device_attributes_response();
+///     // write `reply` to the pty
+/// }
+/// ```
+#[inline]
+#[must_use]
+pub fn device_attributes_response() -> Vec<u8> {
+    DEVICE_ATTRIBUTES.to_vec()
+}
+
+/// Returns whether the output of a program contains a primary device attributes query.
+///
+/// Both `\x1b[c` and `\x1b[0c` are recognized. Queries split across two reads of the output
+/// are not detected.
+#[must_use]
+pub fn contains_device_attributes_request(output: &[u8]) -> bool {
+    const REQUESTS: [&[u8]; 2] = [b"\x1b[c", b"\x1b[0c"];
+    REQUESTS.iter().any(|request| {
+        output
+            .windows(request.len())
+            .any(|window| window == *request)
+    })
+}
+
+/// Returns the reply to a cursor position query (`\x1b[6n`).
+///
+/// `row` and `col` are zero based, as returned by
+/// [`Screen::cursor_position`](crate::widget::Screen::cursor_position),
+/// the report itself is one based.
+/// Programs like `bash` query the position, e.g. to place the prompt.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::{input::cursor_position_report, widget::Screen};
+///
+/// let mut parser = vt100::Parser::new(24, 80, 0);
+/// parser.process(b"hello");
+/// let (row, col) = parser.screen().cursor_position();
+/// assert_eq!(cursor_position_report(row, col), b"\x1b[1;6R");
+/// ```
+#[inline]
+#[must_use]
+pub fn cursor_position_report(row: u16, col: u16) -> Vec<u8> {
+    format!("\x1b[{};{}R", u32::from(row) + 1, u32::from(col) + 1).into_bytes()
+}
+
+/// Returns whether the output of a program contains a cursor position query (`\x1b[6n`).
+///
+/// Queries split across two reads of the output are not detected.
+#[must_use]
+pub fn contains_cursor_position_request(output: &[u8]) -> bool {
+    const REQUEST: &[u8] = b"\x1b[6n";
+    output
+        .windows(REQUEST.len())
+        .any(|window| window == REQUEST)
+}
+
+/// Returns the reply to a text area size query (`\x1b[18t`).
+///
+/// `rows` and `cols` are the size of the screen, as returned by
+/// [`Screen::size`](crate::widget::Screen::size). Programs query the size to lay out their
+/// output, e.g. when `TIOCGWINSZ` isn't available over a serial line.
+///
+/// Only the size in characters (`18t`) is supported. Queries of the size in pixels
+/// (`14t`, `16t`) can't be answered, as the cell size of the host terminal is unknown, and
+/// neither can the size of the host screen (`19t`).
+///
+/// # Example
+///
+/// ```
+/// use tui_term::{
+///     input::{contains_window_size_request, window_size_report},
+///     widget::Screen,
+/// };
+///
+/// let parser = vt100::Parser::new(24, 80, 0);
+/// if contains_window_size_request(b"\x1b[18t") {
+///     let (rows, cols) = parser.screen().size();
+///     assert_eq!(window_size_report(rows, cols), b"\x1b[8;24;80t");
+/// }
+/// ```
+#[inline]
+#[must_use]
+pub fn window_size_report(rows: u16, cols: u16) -> Vec<u8> {
+    format!("\x1b[8;{rows};{cols}t").into_bytes()
+}
+
+/// Returns whether the output of a program contains a text area size query (`\x1b[18t`).
+///
+/// Queries split across two reads of the output are not detected.
+#[must_use]
+pub fn contains_window_size_request(output: &[u8]) -> bool {
+    const REQUEST: &[u8] = b"\x1b[18t";
+    output
+        .windows(REQUEST.len())
+        .any(|window| window == REQUEST)
+}
+
+/// Start of a paste in bracketed paste mode.
+pub const PASTE_START: &[u8] = b"\x1b[200~";
+/// End of a paste in bracketed paste mode.
+pub const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// Returns the sequence for pasting `text`.
+///
+/// `bracketed` is whether the program enabled bracketed paste mode (`\x1b[?2004h`). The text
+/// is then wrapped in [`PASTE_START`] and [`PASTE_END`], so that the program doesn't execute
+/// pasted commands. Escape characters are dropped from the text, so that it can't end the
+/// paste early.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::input::paste_sequence;
+///
+/// assert_eq!(paste_sequence("ls\n", true), b"\x1b[200~ls\n\x1b[201~");
+/// assert_eq!(paste_sequence("ls\n", false), b"ls\n");
+/// ```
+#[must_use]
+pub fn paste_sequence(text: &str, bracketed: bool) -> Vec<u8> {
+    if !bracketed {
+        return text.as_bytes().to_vec();
+    }
+    let mut sequence = PASTE_START.to_vec();
+    sequence.extend(text.bytes().filter(|&b| b != 0x1b));
+    sequence.extend_from_slice(PASTE_END);
+    sequence
+}
+
+/// Returns the SGR mouse report (`\x1b[?1006h`) of a button at (row, column).
+///
+/// `button` is the button code, e.g. `0` for the left button, `32` added while dragging or
+/// `64` for scrolling up, `modifiers` are added to it. `row` and `col` are zero based, the
+/// report is one based. A release is reported for the released button.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::input::{mouse_sgr_sequence, KeyModifiers};
+///
+/// let click = mouse_sgr_sequence(0, KeyModifiers::default(), 4, 9, true);
+/// assert_eq!(click, b"\x1b[<0;10;5M");
+/// ```
+#[inline]
+#[must_use]
+pub fn mouse_sgr_sequence(
+    button: u8,
+    modifiers: KeyModifiers,
+    row: u16,
+    col: u16,
+    pressed: bool,
+) -> Vec<u8> {
+    let button = u32::from(button)
+        + 4 * u32::from(modifiers.shift)
+        + 8 * u32::from(modifiers.alt)
+        + 16 * u32::from(modifiers.ctrl);
+    let end = if pressed { 'M' } else { 'm' };
+    format!(
+        "\x1b[<{button};{};{}{end}",
+        u32::from(col) + 1,
+        u32::from(row) + 1
+    )
+    .into_bytes()
+}
+
+/// Returns the mouse report of a button at (row, column) in the given encoding.
+///
+/// The SGR encoding is built by [`mouse_sgr_sequence`]. The default and UTF-8 encodings
+/// (`\x1b[M` followed by the button and the one based column and row, offset by 32) can't
+/// tell which button was released, releases are reported as button `3`. Returns `None` if
+/// the position can't be encoded, i.e. is beyond column or row 223 (2015 in UTF-8).
+///
+/// # Example
+///
+/// ```
+/// use tui_term::input::{mouse_sequence, KeyModifiers, MouseEncoding};
+///
+/// let none = KeyModifiers::default();
+/// let click = mouse_sequence(0, none, 4, 9, true, MouseEncoding::Default);
+/// assert_eq!(click, Some(b"\x1b[M *%".to_vec()));
+/// ```
+#[must_use]
+pub fn mouse_sequence(
+    button: u8,
+    modifiers: KeyModifiers,
+    row: u16,
+    col: u16,
+    pressed: bool,
+    encoding: MouseEncoding,
+) -> Option<Vec<u8>> {
+    if encoding == MouseEncoding::Sgr {
+        return Some(mouse_sgr_sequence(button, modifiers, row, col, pressed));
+    }
+    // The low bits of the button code are 3 for a release, the drag and wheel bits are kept
+    let button = if pressed { button } else { button | 3 };
+    let button = u32::from(button)
+        + 4 * u32::from(modifiers.shift)
+        + 8 * u32::from(modifiers.alt)
+        + 16 * u32::from(modifiers.ctrl);
+    let mut sequence = b"\x1b[M".to_vec();
+    for value in [button, u32::from(col) + 1, u32::from(row) + 1] {
+        let value = value + 32;
+        match encoding {
+            MouseEncoding::Utf8 if value < 0x800 => {
+                let c = char::from_u32(value)?;
+                sequence.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+            }
+            MouseEncoding::Default if value < 0x100 => sequence.push(value as u8),
+            _ => return None,
+        }
+    }
+    Some(sequence)
+}
+
+/// The mouse events a program asked for, see [`EncodeFlags::mouse_mode`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseMode {
+    /// Nothing is reported
+    #[default]
+    None,
+    /// Button presses are reported, without modifiers (X10, `\x1b[?9h`)
+    Press,
+    /// Button presses, releases and the wheel are reported (`\x1b[?1000h`)
+    PressRelease,
+    /// Additionally, moves while a button is held are reported (`\x1b[?1002h`)
+    ButtonMotion,
+    /// Additionally, every move is reported (`\x1b[?1003h`)
+    AnyMotion,
+}
+
+/// How a program asked mouse events to be encoded, see [`EncodeFlags::mouse_encoding`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseEncoding {
+    /// `\x1b[M` followed by one byte each for the button, column and row
+    #[default]
+    Default,
+    /// Like the default, with UTF-8 encoded values for large screens (`\x1b[?1005h`)
+    Utf8,
+    /// `\x1b[<b;x;yM`, see [`mouse_sgr_sequence`] (`\x1b[?1006h`)
+    Sgr,
+}
+
+#[cfg(feature = "vt100")]
+impl From<vt100::MouseProtocolMode> for MouseMode {
+    #[inline]
+    fn from(mode: vt100::MouseProtocolMode) -> Self {
+        match mode {
+            vt100::MouseProtocolMode::None => Self::None,
+            vt100::MouseProtocolMode::Press => Self::Press,
+            vt100::MouseProtocolMode::PressRelease => Self::PressRelease,
+            vt100::MouseProtocolMode::ButtonMotion => Self::ButtonMotion,
+            vt100::MouseProtocolMode::AnyMotion => Self::AnyMotion,
+        }
+    }
+}
+
+#[cfg(feature = "vt100")]
+impl From<vt100::MouseProtocolEncoding> for MouseEncoding {
+    #[inline]
+    fn from(encoding: vt100::MouseProtocolEncoding) -> Self {
+        match encoding {
+            vt100::MouseProtocolEncoding::Default => Self::Default,
+            vt100::MouseProtocolEncoding::Utf8 => Self::Utf8,
+            vt100::MouseProtocolEncoding::Sgr => Self::Sgr,
+        }
+    }
+}
+
+/// The modes the program enabled, that decide how [`encode_event`] encodes events.
+///
+/// # Example
+///
+/// ```
+/// use ratatui::layout::Rect;
+/// use tui_term::input::EncodeFlags;
+///
+/// let parser = vt100::Parser::new(24, 80, 0);
+/// let screen = parser.screen();
+/// let flags = EncodeFlags::default()
+///     .bracketed_paste(screen.bracketed_paste())
+///     .mouse_mode(screen.mouse_protocol_mode().into())
+///     .mouse_encoding(screen.mouse_protocol_encoding().into())
+///     .mouse_area(Rect::new(0, 1, 80, 24));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EncodeFlags {
+    pub(crate) focus_reporting: bool,
+    pub(crate) bracketed_paste: bool,
+    pub(crate) mouse_mode: MouseMode,
+    pub(crate) mouse_encoding: MouseEncoding,
+    pub(crate) alt_scroll: bool,
+    pub(crate) application_cursor: bool,
+    pub(crate) application_keypad: bool,
+    pub(crate) mouse_area: Option<Rect>,
+}
+
+impl EncodeFlags {
+    /// Set whether focus changes are reported (default = false),
+    /// see [`Screen::focus_reporting`](crate::widget::Screen::focus_reporting).
+    #[inline]
+    #[must_use]
+    pub const fn focus_reporting(mut self, enabled: bool) -> Self {
+        self.focus_reporting = enabled;
+        self
+    }
+
+    /// Set whether pastes are bracketed (default = false), see [`paste_sequence`].
+    #[inline]
+    #[must_use]
+    pub const fn bracketed_paste(mut self, enabled: bool) -> Self {
+        self.bracketed_paste = enabled;
+        self
+    }
+
+    /// Set which mouse events are reported (default = [`MouseMode::None`]).
+    #[inline]
+    #[must_use]
+    pub const fn mouse_mode(mut self, mode: MouseMode) -> Self {
+        self.mouse_mode = mode;
+        self
+    }
+
+    /// Set how mouse events are encoded (default = [`MouseEncoding::Default`]),
+    /// see [`mouse_sequence`].
+    #[inline]
+    #[must_use]
+    pub const fn mouse_encoding(mut self, encoding: MouseEncoding) -> Self {
+        self.mouse_encoding = encoding;
+        self
+    }
+
+    /// Set whether the mouse wheel sends arrow keys, while mouse buttons aren't reported
+    /// (default = false), see [`alt_scroll_keys`].
+    #[inline]
+    #[must_use]
+    pub const fn alt_scroll(mut self, enabled: bool) -> Self {
+        self.alt_scroll = enabled;
+        self
+    }
+
+    /// Set whether the cursor keys are sent SS3 prefixed (default = false),
+    /// see [`keypad_key_sequence`].
+    #[inline]
+    #[must_use]
+    pub const fn application_cursor(mut self, enabled: bool) -> Self {
+        self.application_cursor = enabled;
+        self
+    }
+
+    /// Set whether the keypad keys are sent SS3 prefixed (default = false),
+    /// see [`keypad_key_sequence`].
+    #[inline]
+    #[must_use]
+    pub const fn application_keypad(mut self, enabled: bool) -> Self {
+        self.application_keypad = enabled;
+        self
+    }
+
+    /// Set the area the screen is drawn to (default = the whole host terminal).
+    ///
+    /// Mouse positions are translated into the area, events outside of it are dropped.
+    /// Without an area, positions are reported unchanged.
+    #[inline]
+    #[must_use]
+    pub const fn mouse_area(mut self, area: Rect) -> Self {
+        self.mouse_area = Some(area);
+        self
+    }
+}
+
+/// Returns the bytes to write to the program for a `crossterm` event.
+///
+/// Keys are encoded by [`keypad_key_sequence`], pastes by [`paste_sequence`], focus
+/// changes by [`focus_event`] and mouse buttons by [`mouse_sequence`]. Without mouse
+/// reporting, the wheel sends the arrow keys of [`alt_scroll_keys`] in the cursor key
+/// mode of the program. Only the mouse events of the [mode](EncodeFlags::mouse_mode) the
+/// program enabled are reported. Resizes return `None`, resize the parser and the pty
+/// instead.
+///
+/// # Example
+///
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+/// use tui_term::input::{encode_event, EncodeFlags};
+///
+/// let event = Event::Key(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+/// assert_eq!(
+///     encode_event(&event, &EncodeFlags::default()),
+///     Some(b"\x1b[A".to_vec())
+/// );
+/// ```
+#[cfg(feature = "crossterm")]
+#[must_use]
+pub fn encode_event(event: &crossterm::event::Event, flags: &EncodeFlags) -> Option<Vec<u8>> {
+    use crossterm::event::{Event, MouseButton, MouseEventKind};
+    match event {
+        Event::Key(key) => {
+            let (key, modifiers) = key_from_crossterm(key)?;
+            let (cursor, keypad) = (flags.application_cursor, flags.application_keypad);
+            keypad_key_sequence(key, modifiers, cursor, keypad)
+        }
+        Event::Paste(text) => Some(paste_sequence(text, flags.bracketed_paste)),
+        Event::FocusGained | Event::FocusLost => {
+            focus_event_from_crossterm(event, flags.focus_reporting)
+        }
+        Event::Mouse(mouse) => {
+            let position = Position::new(mouse.column, mouse.row);
+            let (row, col) = match flags.mouse_area {
+                Some(area) if !area.contains(position) => return None,
+                Some(area) => (position.y - area.y, position.x - area.x),
+                None => (position.y, position.x),
+            };
+            let code = |button: MouseButton| match button {
+                MouseButton::Left => 0,
+                MouseButton::Middle => 1,
+                MouseButton::Right => 2,
+            };
+            let mode = flags.mouse_mode;
+            let (button, pressed) = match mouse.kind {
+                MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+                    if matches!(mode, MouseMode::None | MouseMode::Press) =>
+                {
+                    if !flags.alt_scroll || mode != MouseMode::None {
+                        return None;
+                    }
+                    // The arrow keys of alternate scroll mode follow DECCKM like typed ones
+                    let key = if mouse.kind == MouseEventKind::ScrollUp {
+                        Key::Up
+                    } else {
+                        Key::Down
+                    };
+                    let none = KeyModifiers::default();
+                    return keypad_key_sequence(key, none, flags.application_cursor, false);
+                }
+                _ if mode == MouseMode::None => return None,
+                MouseEventKind::Down(button) => (code(button), true),
+                _ if mode == MouseMode::Press => return None,
+                MouseEventKind::Up(button) => (code(button), false),
+                MouseEventKind::ScrollUp => (64, true),
+                MouseEventKind::ScrollDown => (65, true),
+                _ if mode == MouseMode::PressRelease => return None,
+                MouseEventKind::Drag(button) => (code(button) + 32, true),
+                MouseEventKind::Moved if mode == MouseMode::AnyMotion => (35, true),
+                _ => return None,
+            };
+            let modifiers = if mode == MouseMode::Press {
+                KeyModifiers::default()
+            } else {
+                mouse.modifiers.into()
+            };
+            let encoding = flags.mouse_encoding;
+            mouse_sequence(button, modifiers, row, col, pressed, encoding)
+        }
+        _ => None,
+    }
+}
+
+/// Modifiers held while a key is pressed.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::input::KeyModifiers;
+///
+/// let modifiers = KeyModifiers::default().ctrl(true).shift(true);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyModifiers {
+    pub(crate) shift: bool,
+    pub(crate) alt: bool,
+    pub(crate) ctrl: bool,
+}
+
+impl KeyModifiers {
+    /// Set whether Shift is held (default = false).
+    #[inline]
+    #[must_use]
+    pub const fn shift(mut self, shift: bool) -> Self {
+        self.shift = shift;
+        self
+    }
+
+    /// Set whether Alt is held (default = false).
+    #[inline]
+    #[must_use]
+    pub const fn alt(mut self, alt: bool) -> Self {
+        self.alt = alt;
+        self
+    }
+
+    /// Set whether Ctrl is held (default = false).
+    #[inline]
+    #[must_use]
+    pub const fn ctrl(mut self, ctrl: bool) -> Self {
+        self.ctrl = ctrl;
+        self
+    }
+
+    /// Whether no modifier is held.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        !(self.shift || self.alt || self.ctrl)
+    }
+
+    /// The xterm modifier parameter of CSI sequences, e.g. `5` for Ctrl.
+    ///
+    /// It is one plus the sum of Shift (1), Alt (2) and Ctrl (4).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::input::KeyModifiers;
+    ///
+    /// assert_eq!(KeyModifiers::default().parameter(), 1);
+    /// assert_eq!(
+    ///     KeyModifiers::default().ctrl(true).shift(true).parameter(),
+    ///     6
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn parameter(self) -> u8 {
+        1 + self.shift as u8 + 2 * self.alt as u8 + 4 * self.ctrl as u8
+    }
+}
+
+/// A key that is encoded by [`key_sequence`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Key {
+    /// A printable character
+    Char(char),
+    /// The enter key
+    Enter,
+    /// The tab key, Shift+Tab is sent as back tab
+    Tab,
+    /// The backspace key
+    Backspace,
+    /// The escape key
+    Esc,
+    /// The up arrow key
+    Up,
+    /// The down arrow key
+    Down,
+    /// The right arrow key
+    Right,
+    /// The left arrow key
+    Left,
+    /// The home key
+    Home,
+    /// The end key
+    End,
+    /// The insert key
+    Insert,
+    /// The delete key
+    Delete,
+    /// The page up key
+    PageUp,
+    /// The page down key
+    PageDown,
+    /// A function key, from `F(1)` to `F(12)`
+    F(u8),
+    /// A key of the numeric keypad: a digit, `.`, `,`, `+`, `-`, `*`, `/`, `=` or `'\r'` for
+    /// its enter key
+    Keypad(char),
+}
+
+/// Returns the sequence of a key pressed with modifiers, following the xterm encoding.
+///
+/// Cursor and function keys carry the [modifier parameter](KeyModifiers::parameter) in their
+/// CSI sequence, e.g. `Ctrl+Left` is `\x1b[1;5D`. Other keys are prefixed with ESC if Alt is
+/// held, and Ctrl turns characters into control characters.
+/// Unmodified cursor keys are sent in normal cursor key mode.
+/// Returns `None` for keys without a sequence, e.g. `F(13)`.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::input::{key_sequence, Key, KeyModifiers};
+///
+/// let ctrl = KeyModifiers::default().ctrl(true);
+/// assert_eq!(key_sequence(Key::Left, ctrl), Some(b"\x1b[1;5D".to_vec()));
+/// assert_eq!(key_sequence(Key::Char('c'), ctrl), Some(b"\x03".to_vec()));
+/// ```
+#[must_use]
+pub fn key_sequence(key: Key, modifiers: KeyModifiers) -> Option<Vec<u8>> {
+    let csi = |code: &str, suffix: char| {
+        let sequence = if modifiers.is_empty() {
+            format!("\x1b[{code}{suffix}")
+        } else {
+            // The code defaults to 1, but it must be present if a modifier follows
+            let code = if code.is_empty() { "1" } else { code };
+            format!("\x1b[{code};{}{suffix}", modifiers.parameter())
+        };
+        Some(sequence.into_bytes())
+    };
+    let bytes: Vec<u8> = match key {
+        Key::Up => return csi("", 'A'),
+        Key::Down => return csi("", 'B'),
+        Key::Right => return csi("", 'C'),
+        Key::Left => return csi("", 'D'),
+        Key::End => return csi("", 'F'),
+        Key::Home => return csi("", 'H'),
+        Key::Insert => return csi("2", '~'),
+        Key::Delete => return csi("3", '~'),
+        Key::PageUp => return csi("5", '~'),
+        Key::PageDown => return csi("6", '~'),
+        Key::F(n @ 1..=4) => {
+            let suffix = char::from(b'P' + n - 1);
+            if modifiers.is_empty() {
+                return Some(format!("\x1bO{suffix}").into_bytes());
+            }
+            return csi("", suffix);
+        }
+        Key::F(n @ 5..=12) => {
+            const CODES: [&str; 8] = ["15", "17", "18", "19", "20", "21", "23", "24"];
+            return csi(CODES[usize::from(n - 5)], '~');
+        }
+        Key::F(_) => return None,
+        Key::Keypad('\r') => return key_sequence(Key::Enter, modifiers),
+        Key::Keypad(c) => return key_sequence(Key::Char(c), modifiers),
+        Key::Tab if modifiers.shift => b"\x1b[Z".to_vec(),
+        Key::Tab => b"\t".to_vec(),
+        Key::Enter => b"\r".to_vec(),
+        Key::Backspace => b"\x7f".to_vec(),
+        Key::Esc => b"\x1b".to_vec(),
+        Key::Char(c) => match c {
+            '@' | 'a'..='z' | 'A'..='Z' | '[' | '\\' | ']' | '^' | '_' if modifiers.ctrl => {
+                vec![c as u8 & 0x1f]
+            }
+            c => c.to_string().into_bytes(),
+        },
+    };
+    // Alt is sent as an ESC prefix
+    Some(if modifiers.alt {
+        [b"\x1b", &bytes[..]].concat()
+    } else {
+        bytes
+    })
+}
+
+/// Returns the sequence of a key pressed with modifiers, like [`key_sequence`], in the key
+/// modes of the program.
+///
+/// `application_cursor` is whether the program enabled application cursor keys (DECCKM,
+/// `\x1b[?1h`), see [`Screen::application_cursor`](crate::widget::Screen::application_cursor).
+/// The cursor keys, Home and End are then sent SS3 prefixed, e.g. Up is `\x1bOA`.
+/// `application_keypad` is whether the program enabled the application keypad (DECKPAM,
+/// `\x1b=`), see [`Screen::application_keypad`](crate::widget::Screen::application_keypad).
+/// The [keypad keys](Key::Keypad) are then sent SS3 prefixed as well, e.g. keypad `1` is
+/// `\x1bOq`. Keys pressed with modifiers are always sent as by [`key_sequence`].
+///
+/// # Example
+///
+/// ```
+/// use tui_term::input::{keypad_key_sequence, Key, KeyModifiers};
+///
+/// let none = KeyModifiers::default();
+/// assert_eq!(
+///     keypad_key_sequence(Key::Up, none, true, false),
+///     Some(b"\x1bOA".to_vec())
+/// );
+/// assert_eq!(
+///     keypad_key_sequence(Key::Up, none, false, true),
+///     Some(b"\x1b[A".to_vec())
+/// );
+/// ```
+#[must_use]
+pub fn keypad_key_sequence(
+    key: Key,
+    modifiers: KeyModifiers,
+    application_cursor: bool,
+    application_keypad: bool,
+) -> Option<Vec<u8>> {
+    let (application, suffix) = match key {
+        Key::Up => (application_cursor, 'A'),
+        Key::Down => (application_cursor, 'B'),
+        Key::Right => (application_cursor, 'C'),
+        Key::Left => (application_cursor, 'D'),
+        Key::End => (application_cursor, 'F'),
+        Key::Home => (application_cursor, 'H'),
+        Key::Keypad(c @ '0'..='9') => (application_keypad, char::from(b'p' + (c as u8 - b'0'))),
+        Key::Keypad(c) => {
+            let suffix = match c {
+                '\r' => 'M',
+                '*' => 'j',
+                '+' => 'k',
+                ',' => 'l',
+                '-' => 'm',
+                '.' => 'n',
+                '/' => 'o',
+                '=' => 'X',
+                _ => return key_sequence(key, modifiers),
+            };
+            (application_keypad, suffix)
+        }
+        _ => return key_sequence(key, modifiers),
+    };
+    if !application || !modifiers.is_empty() {
+        return key_sequence(key, modifiers);
+    }
+    Some(format!("\x1bO{suffix}").into_bytes())
+}
+
+/// Returns the sequence for a `crossterm` focus event.
+///
+/// Returns `None` for any other event, or if focus reporting is disabled.
+#[cfg(feature = "crossterm")]
+#[inline]
+#[must_use]
+pub fn focus_event_from_crossterm(
+    event: &crossterm::event::Event,
+    enabled: bool,
+) -> Option<Vec<u8>> {
+    use crossterm::event::Event;
+    match event {
+        Event::FocusGained => focus_event(true, enabled),
+        Event::FocusLost => focus_event(false, enabled),
+        _ => None,
+    }
+}
+
+/// Returns the sequence for a `crossterm` key event, see [`key_sequence`].
+///
+/// Returns `None` for key releases and keys without a sequence.
+#[cfg(feature = "crossterm")]
+#[must_use]
+pub fn key_event_from_crossterm(event: &crossterm::event::KeyEvent) -> Option<Vec<u8>> {
+    let (key, modifiers) = key_from_crossterm(event)?;
+    key_sequence(key, modifiers)
+}
+
+/// The key and modifiers of a `crossterm` key event, `None` for releases and unknown keys.
+#[cfg(feature = "crossterm")]
+fn key_from_crossterm(event: &crossterm::event::KeyEvent) -> Option<(Key, KeyModifiers)> {
+    use crossterm::event::{KeyCode, KeyEventKind, KeyEventState};
+    if event.kind == KeyEventKind::Release {
+        return None;
+    }
+    let modifiers = KeyModifiers::from(event.modifiers);
+    // Only reported with the keyboard enhancement flags of crossterm
+    let keypad = event.state.contains(KeyEventState::KEYPAD);
+    let key = match event.code {
+        KeyCode::Char(c @ ('0'..='9' | '.' | ',' | '+' | '-' | '*' | '/' | '=')) if keypad => {
+            Key::Keypad(c)
+        }
+        KeyCode::Enter if keypad => Key::Keypad('\r'),
+        KeyCode::Char(c) => Key::Char(c),
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::BackTab => return Some((Key::Tab, modifiers.shift(true))),
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Esc => Key::Esc,
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Right => Key::Right,
+        KeyCode::Left => Key::Left,
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+        KeyCode::Insert => Key::Insert,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::PageDown => Key::PageDown,
+        KeyCode::F(n) => Key::F(n),
+        _ => return None,
+    };
+    Some((key, modifiers))
+}
+
+#[cfg(feature = "crossterm")]
+impl From<crossterm::event::KeyModifiers> for KeyModifiers {
+    #[inline]
+    fn from(modifiers: crossterm::event::KeyModifiers) -> Self {
+        use crossterm::event::KeyModifiers as Modifiers;
+        Self::default()
+            .shift(modifiers.contains(Modifiers::SHIFT))
+            .alt(modifiers.contains(Modifiers::ALT))
+            .ctrl(modifiers.contains(Modifiers::CONTROL))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn focus_sequences() {
+        assert_eq!(focus_event(true, true).as_deref(), Some(&b"\x1b[I"[..]));
+        assert_eq!(focus_event(false, true).as_deref(), Some(&b"\x1b[O"[..]));
+        assert_eq!(focus_event(true, false), None);
+        assert_eq!(focus_event(false, false), None);
+    }
+
+    #[test]
+    fn alt_scroll_arrow_keys() {
+        assert_eq!(alt_scroll_keys(true, 1, true).as_deref(), Some(ARROW_UP));
+        assert_eq!(
+            alt_scroll_keys(false, 3, true).as_deref(),
+            Some(&b"\x1b[B\x1b[B\x1b[B"[..])
+        );
+        assert_eq!(alt_scroll_keys(true, 0, true).as_deref(), Some(&b""[..]));
+        assert_eq!(alt_scroll_keys(true, 1, false), None);
+    }
+
+    #[test]
+    fn device_attributes() {
+        assert_eq!(device_attributes_response(), b"\x1b[?62;22c");
+        assert!(contains_device_attributes_request(b"foo\x1b[cbar"));
+        assert!(contains_device_attributes_request(b"\x1b[0c"));
+        // Secondary device attributes and similar sequences are not primary queries
+        assert!(!contains_device_attributes_request(b"\x1b[>c"));
+        assert!(!contains_device_attributes_request(b"\x1b[1;2c"));
+        assert!(!contains_device_attributes_request(b""));
+    }
+
+    #[test]
+    fn cursor_position_reports() {
+        assert_eq!(cursor_position_report(0, 0), b"\x1b[1;1R");
+        assert_eq!(cursor_position_report(4, 11), b"\x1b[5;12R");
+        assert_eq!(cursor_position_report(u16::MAX, 0), b"\x1b[65536;1R");
+        assert!(contains_cursor_position_request(b"$ \x1b[6n"));
+        assert!(!contains_cursor_position_request(b"\x1b[5n"));
+    }
+
+    #[test]
+    fn window_size_reports() {
+        assert_eq!(window_size_report(24, 80), b"\x1b[8;24;80t");
+        assert_eq!(window_size_report(1, u16::MAX), b"\x1b[8;1;65535t");
+        assert!(contains_window_size_request(b"vim\x1b[18t"));
+        assert!(!contains_window_size_request(b"\x1b[14t"));
+        assert!(!contains_window_size_request(b"\x1b[8;24;80t"));
+    }
+
+    #[test]
+    fn modified_key_sequences() {
+        let none = KeyModifiers::default();
+        let shift = none.shift(true);
+        let ctrl = none.ctrl(true);
+        let alt = none.alt(true);
+        assert_eq!(key_sequence(Key::Up, none).as_deref(), Some(ARROW_UP));
+        assert_eq!(
+            key_sequence(Key::Up, shift).as_deref(),
+            Some(&b"\x1b[1;2A"[..])
+        );
+        assert_eq!(
+            key_sequence(Key::Right, ctrl).as_deref(),
+            Some(&b"\x1b[1;5C"[..])
+        );
+        assert_eq!(
+            key_sequence(Key::Enter, alt).as_deref(),
+            Some(&b"\x1b\r"[..])
+        );
+        assert_eq!(
+            key_sequence(Key::Delete, ctrl.shift(true)).as_deref(),
+            Some(&b"\x1b[3;6~"[..])
+        );
+        assert_eq!(
+            key_sequence(Key::F(1), none).as_deref(),
+            Some(&b"\x1bOP"[..])
+        );
+        assert_eq!(
+            key_sequence(Key::F(2), alt).as_deref(),
+            Some(&b"\x1b[1;3Q"[..])
+        );
+        assert_eq!(
+            key_sequence(Key::F(12), none).as_deref(),
+            Some(&b"\x1b[24~"[..])
+        );
+        assert_eq!(key_sequence(Key::F(13), none), None);
+        assert_eq!(
+            key_sequence(Key::Tab, shift).as_deref(),
+            Some(&b"\x1b[Z"[..])
+        );
+        assert_eq!(
+            key_sequence(Key::Char('c'), ctrl.alt(true)).as_deref(),
+            Some(&b"\x1b\x03"[..])
+        );
+        assert_eq!(key_sequence(Key::Char('é'), none), Some("é".into()));
+    }
+
+    #[test]
+    fn keypad_key_sequences() {
+        let none = KeyModifiers::default();
+        let sequence = |key: Key, modifiers: KeyModifiers, cursor: bool, keypad: bool| {
+            keypad_key_sequence(key, modifiers, cursor, keypad).unwrap()
+        };
+        // Cursor keys follow DECCKM only
+        assert_eq!(sequence(Key::Up, none, false, false), b"\x1b[A");
+        assert_eq!(sequence(Key::Up, none, true, false), b"\x1bOA");
+        assert_eq!(sequence(Key::Up, none, false, true), b"\x1b[A");
+        assert_eq!(sequence(Key::End, none, true, false), b"\x1bOF");
+        let shift = KeyModifiers::default().shift(true);
+        assert_eq!(sequence(Key::Up, shift, true, false), b"\x1b[1;2A");
+
+        // Keypad keys follow DECKPAM only
+        assert_eq!(sequence(Key::Keypad('1'), none, false, false), b"1");
+        assert_eq!(sequence(Key::Keypad('1'), none, true, false), b"1");
+        assert_eq!(sequence(Key::Keypad('1'), none, false, true), b"\x1bOq");
+        assert_eq!(sequence(Key::Keypad('\r'), none, false, false), b"\r");
+        assert_eq!(sequence(Key::Keypad('\r'), none, false, true), b"\x1bOM");
+        assert_eq!(sequence(Key::Keypad('-'), none, false, true), b"\x1bOm");
+        assert_eq!(sequence(Key::Enter, none, true, true), b"\r");
+    }
+
+    #[test]
+    fn paste_and_mouse_sequences() {
+        assert_eq!(
+            paste_sequence("a\x1b[201~b", true),
+            b"\x1b[200~a[201~b\x1b[201~"
+        );
+        assert_eq!(
+            mouse_sgr_sequence(2, KeyModifiers::default().ctrl(true), 0, 0, false),
+            b"\x1b[<18;1;1m"
+        );
+        let none = KeyModifiers::default();
+        assert_eq!(
+            mouse_sequence(65, none, 0, 1, true, MouseEncoding::Default).unwrap(),
+            b"\x1b[Ma\x22!"
+        );
+        assert_eq!(
+            mouse_sequence(0, none, 0, 300, true, MouseEncoding::Default),
+            None
+        );
+        assert_eq!(
+            mouse_sequence(0, none, 0, 300, true, MouseEncoding::Utf8).unwrap(),
+            "\x1b[M \u{14d}!".as_bytes()
+        );
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[test]
+    fn encode_crossterm_events() {
+        use crossterm::event::{
+            Event, KeyCode, KeyEvent, KeyEventState, KeyModifiers as Modifiers, MouseButton,
+            MouseEvent, MouseEventKind,
+        };
+
+        let flags = EncodeFlags::default();
+        let key = Event::Key(KeyEvent::new(KeyCode::Char('c'), Modifiers::CONTROL));
+        assert_eq!(encode_event(&key, &flags).as_deref(), Some(&b"\x03"[..]));
+
+        let up = Event::Key(KeyEvent::new(KeyCode::Up, Modifiers::NONE));
+        assert_eq!(encode_event(&up, &flags).as_deref(), Some(&b"\x1b[A"[..]));
+        let keypad = flags.application_keypad(true);
+        assert_eq!(encode_event(&up, &keypad).as_deref(), Some(&b"\x1b[A"[..]));
+        let cursor = flags.application_cursor(true);
+        assert_eq!(encode_event(&up, &cursor).as_deref(), Some(&b"\x1bOA"[..]));
+        let digit = Event::Key(KeyEvent {
+            state: KeyEventState::KEYPAD,
+            ..KeyEvent::new(KeyCode::Char('1'), Modifiers::NONE)
+        });
+        assert_eq!(encode_event(&digit, &cursor).as_deref(), Some(&b"1"[..]));
+        assert_eq!(
+            encode_event(&digit, &keypad).as_deref(),
+            Some(&b"\x1bOq"[..])
+        );
+
+        let paste = Event::Paste("echo hi".to_string());
+        assert_eq!(
+            encode_event(&paste, &flags).as_deref(),
+            Some(&b"echo hi"[..])
+        );
+        let bracketed = flags.bracketed_paste(true);
+        assert_eq!(
+            encode_event(&paste, &bracketed).as_deref(),
+            Some(&b"\x1b[200~echo hi\x1b[201~"[..])
+        );
+
+        let mouse = |kind: MouseEventKind, column: u16, row: u16| {
+            Event::Mouse(MouseEvent {
+                kind,
+                column,
+                row,
+                modifiers: Modifiers::NONE,
+            })
+        };
+        let click = mouse(MouseEventKind::Down(MouseButton::Left), 12, 3);
+        assert_eq!(encode_event(&click, &flags), None);
+        let reporting = flags
+            .mouse_mode(MouseMode::PressRelease)
+            .mouse_encoding(MouseEncoding::Sgr)
+            .mouse_area(Rect::new(2, 1, 80, 24));
+        assert_eq!(
+            encode_event(&click, &reporting).as_deref(),
+            Some(&b"\x1b[<0;11;3M"[..])
+        );
+        let release = mouse(MouseEventKind::Up(MouseButton::Left), 12, 3);
+        assert_eq!(
+            encode_event(&release, &reporting).as_deref(),
+            Some(&b"\x1b[<0;11;3m"[..])
+        );
+        let drag = mouse(MouseEventKind::Drag(MouseButton::Left), 12, 3);
+        assert_eq!(encode_event(&drag, &reporting), None);
+        let moved = mouse(MouseEventKind::Moved, 12, 3);
+        assert_eq!(encode_event(&moved, &reporting), None);
+        let motion = reporting.mouse_mode(MouseMode::ButtonMotion);
+        assert_eq!(
+            encode_event(&drag, &motion).as_deref(),
+            Some(&b"\x1b[<32;11;3M"[..])
+        );
+        assert_eq!(encode_event(&moved, &motion), None);
+        let any_motion = reporting.mouse_mode(MouseMode::AnyMotion);
+        assert_eq!(
+            encode_event(&moved, &any_motion).as_deref(),
+            Some(&b"\x1b[<35;11;3M"[..])
+        );
+        let outside = mouse(MouseEventKind::Down(MouseButton::Left), 1, 3);
+        assert_eq!(encode_event(&outside, &reporting), None);
+        // Without an area, positions past any clamped rectangle are reported as they are
+        let wide = mouse(MouseEventKind::Down(MouseButton::Left), 300, 3);
+        let unbounded = flags
+            .mouse_mode(MouseMode::PressRelease)
+            .mouse_encoding(MouseEncoding::Sgr);
+        assert_eq!(
+            encode_event(&wide, &unbounded).as_deref(),
+            Some(&b"\x1b[<0;301;4M"[..])
+        );
+
+        // Without SGR, the default encoding and X10 mode
+        let default = reporting.mouse_encoding(MouseEncoding::Default);
+        assert_eq!(
+            encode_event(&click, &default).as_deref(),
+            Some(&b"\x1b[M +#"[..])
+        );
+        assert_eq!(
+            encode_event(&release, &default).as_deref(),
+            Some(&b"\x1b[M#+#"[..])
+        );
+        let x10 = default.mouse_mode(MouseMode::Press);
+        assert_eq!(
+            encode_event(&click, &x10).as_deref(),
+            Some(&b"\x1b[M +#"[..])
+        );
+        assert_eq!(encode_event(&release, &x10), None);
+        let wheel = mouse(MouseEventKind::ScrollUp, 12, 3);
+        assert_eq!(
+            encode_event(&wheel, &flags.alt_scroll(true)).as_deref(),
+            Some(ARROW_UP)
+        );
+        let application = flags.alt_scroll(true).application_cursor(true);
+        assert_eq!(
+            encode_event(&wheel, &application).as_deref(),
+            Some(&b"\x1bOA"[..])
+        );
+        let wheel_down = mouse(MouseEventKind::ScrollDown, 12, 3);
+        assert_eq!(
+            encode_event(&wheel_down, &application).as_deref(),
+            Some(&b"\x1bOB"[..])
+        );
+        assert_eq!(encode_event(&wheel, &flags), None);
+        assert_eq!(encode_event(&Event::Resize(80, 24), &reporting), None);
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[test]
+    fn key_sequences_from_crossterm() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers as Modifiers};
+
+        let event = KeyEvent::new(KeyCode::Left, Modifiers::CONTROL);
+        assert_eq!(
+            key_event_from_crossterm(&event).as_deref(),
+            Some(&b"\x1b[1;5D"[..])
+        );
+        let event = KeyEvent::new(KeyCode::BackTab, Modifiers::SHIFT);
+        assert_eq!(
+            key_event_from_crossterm(&event).as_deref(),
+            Some(&b"\x1b[Z"[..])
+        );
+        let event = KeyEvent::new(KeyCode::CapsLock, Modifiers::NONE);
+        assert_eq!(key_event_from_crossterm(&event), None);
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[test]
+    fn focus_sequences_from_crossterm() {
+        use crossterm::event::Event;
+
+        assert_eq!(
+            focus_event_from_crossterm(&Event::FocusLost, true).as_deref(),
+            Some(FOCUS_OUT)
+        );
+        assert_eq!(focus_event_from_crossterm(&Event::FocusGained, false), None);
+        assert_eq!(focus_event_from_crossterm(&Event::Resize(1, 1), true), None);
+    }
+}
@@ -48,7 +48,17 @@
 //! - The `vt100` crate is currently the only supported backend for parsing terminal control
 //!   sequences, but future versions may introduce support for alternative backends.
 
+pub mod cast;
+pub mod diagnostics;
+pub mod export;
+pub mod grid;
+pub mod input;
+mod palette;
+#[cfg(feature = "vt100")]
+pub mod playback;
+pub mod snapshot;
 mod state;
+pub mod terminfo;
 #[cfg(feature = "vt100")]
 mod vt100_imp;
 pub mod widget;
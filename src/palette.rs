@@ -0,0 +1,93 @@
+//! Resolution of terminal colors to RGB.
+
+use ratatui::style::Color;
+
+/// The default RGB values of the 16 ANSI colors, as used by xterm.
+const ANSI: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// The RGB value of an entry of the 256 color palette.
+///
+/// The first 16 entries are the ANSI colors, followed by a 6x6x6 color cube
+/// and a grayscale ramp.
+pub(crate) fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI[usize::from(index)],
+        16..=231 => {
+            let index = index - 16;
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            (level(index / 36), level(index / 6 % 6), level(index % 6))
+        }
+        232..=255 => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// The RGB value of a color, `None` for [`Color::Reset`] as it depends on the host terminal.
+pub(crate) fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(index) => Some(indexed_rgb(index)),
+        named => ansi_index(named).map(indexed_rgb),
+    }
+}
+
+/// The index of one of the 16 ANSI colors, named or indexed, `None` for other colors.
+pub(crate) const fn ansi_index(color: Color) -> Option<u8> {
+    match color {
+        Color::Indexed(index @ 0..=15) => Some(index),
+        Color::Black => Some(0),
+        Color::Red => Some(1),
+        Color::Green => Some(2),
+        Color::Yellow => Some(3),
+        Color::Blue => Some(4),
+        Color::Magenta => Some(5),
+        Color::Cyan => Some(6),
+        Color::Gray => Some(7),
+        Color::DarkGray => Some(8),
+        Color::LightRed => Some(9),
+        Color::LightGreen => Some(10),
+        Color::LightYellow => Some(11),
+        Color::LightBlue => Some(12),
+        Color::LightMagenta => Some(13),
+        Color::LightCyan => Some(14),
+        Color::White => Some(15),
+        _ => None,
+    }
+}
+
+/// Blend `color` towards `background`, keeping `opacity` of the color.
+///
+/// Colors that can't be resolved to RGB are kept as they are.
+pub(crate) fn blend(color: Color, background: (u8, u8, u8), opacity: f32) -> Color {
+    let Some((r, g, b)) = rgb(color) else {
+        return color;
+    };
+    let mix = |value: u8, background: u8| {
+        (f32::from(value) * opacity + f32::from(background) * (1.0 - opacity)).round() as u8
+    };
+    Color::Rgb(
+        mix(r, background.0),
+        mix(g, background.1),
+        mix(b, background.2),
+    )
+}
@@ -0,0 +1,111 @@
+//! Playback of recorded terminal output, e.g. for demos.
+
+use std::time::Duration;
+
+/// Replays recorded chunks of output, like asciinema.
+///
+/// The player wraps a [`vt100::Parser`] and feeds it every chunk, whose timestamp is not
+/// after the elapsed time. Seeking backwards replays the recording from the start.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use tui_term::{playback::FramePlayer, widget::PseudoTerminal};
+///
+/// let chunks = vec![
+///     (Duration::ZERO, b"$ ls\r\n".to_vec()),
+///     (Duration::from_millis(500), b"Cargo.toml  src\r\n".to_vec()),
+/// ];
+/// let mut player = FramePlayer::new(24, 80, chunks);
+/// let screen = player.screen_at(Duration::from_millis(100));
+/// let pseudo_term = PseudoTerminal::new(screen);
+/// ```
+pub struct FramePlayer {
+    parser: vt100::Parser,
+    chunks: Vec<(Duration, Vec<u8>)>,
+    next: usize,
+    size: (u16, u16),
+}
+
+impl FramePlayer {
+    /// Creates a new player of the given size.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows`, `cols`: The size of the recorded terminal.
+    /// * `chunks`: The output, with the time it was written at since the start of the recording.
+    ///   Chunks out of order are sorted by their time.
+    #[must_use]
+    pub fn new(
+        rows: u16,
+        cols: u16,
+        chunks: impl IntoIterator<Item = (Duration, Vec<u8>)>,
+    ) -> Self {
+        let mut chunks: Vec<_> = chunks.into_iter().collect();
+        chunks.sort_by_key(|(time, _)| *time);
+        Self {
+            parser: vt100::Parser::new(rows, cols, 0),
+            chunks,
+            next: 0,
+            size: (rows, cols),
+        }
+    }
+
+    /// Returns the screen after all chunks up to the elapsed time were written.
+    pub fn screen_at(&mut self, elapsed: Duration) -> &vt100::Screen {
+        if self.next > 0 && self.chunks[self.next - 1].0 > elapsed {
+            let (rows, cols) = self.size;
+            self.parser = vt100::Parser::new(rows, cols, 0);
+            self.next = 0;
+        }
+        while let Some((time, bytes)) = self.chunks.get(self.next) {
+            if *time > elapsed {
+                break;
+            }
+            self.parser.process(bytes);
+            self.next += 1;
+        }
+        self.parser.screen()
+    }
+
+    /// The time of the last chunk.
+    #[inline]
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.chunks.last().map_or(Duration::ZERO, |(time, _)| *time)
+    }
+
+    /// Whether every chunk was written.
+    #[inline]
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.next == self.chunks.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plays_chunks_up_to_the_elapsed_time() {
+        let chunks = vec![
+            (Duration::from_millis(200), b"two".to_vec()),
+            (Duration::ZERO, b"one\r\n".to_vec()),
+        ];
+        let mut player = FramePlayer::new(2, 10, chunks);
+        assert_eq!(player.duration(), Duration::from_millis(200));
+
+        let contents = |player: &mut FramePlayer, millis| {
+            player.screen_at(Duration::from_millis(millis)).contents()
+        };
+        assert_eq!(contents(&mut player, 100), "one");
+        assert!(!player.is_finished());
+        assert_eq!(contents(&mut player, 200), "one\ntwo");
+        assert!(player.is_finished());
+        // Seeking backwards replays from the start
+        assert_eq!(contents(&mut player, 0), "one");
+    }
+}
@@ -0,0 +1,359 @@
+//! Owned snapshots of a [`Screen`].
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+
+use crate::widget::{cell_width, Cell, Charset, PseudoTerminal, Screen};
+
+/// An owned copy of the cells, styles and cursor of a [`Screen`].
+///
+/// A snapshot doesn't borrow the screen it was taken from, so it can outlive the parser,
+/// e.g. to keep showing the last state of a process after it exited.
+/// It implements [`Screen`] itself and renders like the original screen.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::{snapshot::ScreenSnapshot, widget::PseudoTerminal};
+///
+/// let mut parser = vt100::Parser::new(24, 80, 0);
+/// parser.process(b"process exited");
+/// let snapshot = ScreenSnapshot::new(parser.screen());
+/// drop(parser);
+/// let pseudo_term = PseudoTerminal::new(&snapshot);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenSnapshot {
+    size: (u16, u16),
+    cells: Vec<SnapshotCell>,
+    wrapped: Vec<bool>,
+    cursor_position: (u16, u16),
+    hide_cursor: bool,
+    focus_reporting: bool,
+    scroll_region: (u16, u16),
+    saved_cursor: Option<(u16, u16)>,
+    alt_scroll: bool,
+    origin_mode: bool,
+    insert_mode: bool,
+    application_cursor: bool,
+    application_keypad: bool,
+    echo_disabled: bool,
+    title: String,
+}
+
+/// A cell of a [`ScreenSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotCell {
+    cell: ratatui::buffer::Cell,
+    has_contents: bool,
+    protected: bool,
+    charset: Charset,
+}
+
+impl ScreenSnapshot {
+    /// Captures the current state of the screen.
+    #[must_use]
+    pub fn new<S: Screen + ?Sized>(screen: &S) -> Self {
+        let size @ (rows, cols) = screen.size();
+        let cells = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .map(|(row, col)| SnapshotCell::new(screen.cell(row, col)))
+            .collect();
+        Self {
+            size,
+            cells,
+            wrapped: (0..rows).map(|row| screen.row_wrapped(row)).collect(),
+            cursor_position: screen.cursor_position(),
+            hide_cursor: screen.hide_cursor(),
+            focus_reporting: screen.focus_reporting(),
+            scroll_region: screen.scroll_region(),
+            saved_cursor: screen.saved_cursor(),
+            alt_scroll: screen.alt_scroll(),
+            origin_mode: screen.origin_mode(),
+            insert_mode: screen.insert_mode(),
+            application_cursor: screen.application_cursor(),
+            application_keypad: screen.application_keypad(),
+            echo_disabled: screen.echo_disabled(),
+            title: screen.title().to_string(),
+        }
+    }
+}
+
+impl SnapshotCell {
+    fn new<C: Cell + ?Sized>(screen_cell: Option<&C>) -> Self {
+        let mut cell = ratatui::buffer::Cell::default();
+        if let Some(screen_cell) = screen_cell {
+            screen_cell.apply(&mut cell);
+        }
+        Self {
+            cell,
+            has_contents: screen_cell.is_some_and(Cell::has_contents),
+            protected: screen_cell.is_some_and(Cell::protected),
+            charset: screen_cell.map_or(Charset::default(), Cell::charset),
+        }
+    }
+}
+
+impl Screen for ScreenSnapshot {
+    type C = SnapshotCell;
+
+    #[inline]
+    fn cell(&self, row: u16, col: u16) -> Option<&Self::C> {
+        let (rows, cols) = self.size;
+        if row >= rows || col >= cols {
+            return None;
+        }
+        self.cells
+            .get(usize::from(row) * usize::from(cols) + usize::from(col))
+    }
+
+    #[inline]
+    fn hide_cursor(&self) -> bool {
+        self.hide_cursor
+    }
+
+    #[inline]
+    fn cursor_position(&self) -> (u16, u16) {
+        self.cursor_position
+    }
+
+    #[inline]
+    fn size(&self) -> (u16, u16) {
+        self.size
+    }
+
+    #[inline]
+    fn row_wrapped(&self, row: u16) -> bool {
+        self.wrapped.get(usize::from(row)).copied().unwrap_or(false)
+    }
+
+    #[inline]
+    fn focus_reporting(&self) -> bool {
+        self.focus_reporting
+    }
+
+    #[inline]
+    fn scroll_region(&self) -> (u16, u16) {
+        self.scroll_region
+    }
+
+    #[inline]
+    fn saved_cursor(&self) -> Option<(u16, u16)> {
+        self.saved_cursor
+    }
+
+    #[inline]
+    fn alt_scroll(&self) -> bool {
+        self.alt_scroll
+    }
+
+    #[inline]
+    fn origin_mode(&self) -> bool {
+        self.origin_mode
+    }
+
+    #[inline]
+    fn insert_mode(&self) -> bool {
+        self.insert_mode
+    }
+
+    #[inline]
+    fn application_cursor(&self) -> bool {
+        self.application_cursor
+    }
+
+    #[inline]
+    fn application_keypad(&self) -> bool {
+        self.application_keypad
+    }
+
+    #[inline]
+    fn echo_disabled(&self) -> bool {
+        self.echo_disabled
+    }
+
+    #[inline]
+    fn title(&self) -> &str {
+        &self.title
+    }
+}
+
+impl Cell for SnapshotCell {
+    #[inline]
+    fn has_contents(&self) -> bool {
+        self.has_contents
+    }
+
+    #[inline]
+    fn apply(&self, cell: &mut ratatui::buffer::Cell) {
+        if self.has_contents {
+            cell.set_symbol(self.cell.symbol());
+        }
+        cell.set_style(Style::reset().patch(self.cell.style()));
+    }
+
+    #[inline]
+    fn contents(&self) -> String {
+        if self.has_contents {
+            self.cell.symbol().to_string()
+        } else {
+            String::new()
+        }
+    }
+
+    #[inline]
+    fn fg(&self) -> Color {
+        self.cell.fg
+    }
+
+    #[inline]
+    fn bg(&self) -> Color {
+        self.cell.bg
+    }
+
+    #[inline]
+    fn protected(&self) -> bool {
+        self.protected
+    }
+
+    #[inline]
+    fn charset(&self) -> Charset {
+        self.charset
+    }
+}
+
+/// A banner announcing that the process exited, drawn over its last screen.
+///
+/// The banner reads "Process exited with code N — press any key" and is centered on the
+/// terminal, which is rendered as configured, e.g. from a [`ScreenSnapshot`] taken when the
+/// child exited.
+///
+/// # Example
+///
+/// ```
+/// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+/// use tui_term::{
+///     snapshot::{ExitOverlay, ScreenSnapshot},
+///     widget::PseudoTerminal,
+/// };
+///
+/// let mut parser = vt100::Parser::new(24, 80, 0);
+/// parser.process(b"$ false");
+/// let snapshot = ScreenSnapshot::new(parser.screen());
+/// let area = Rect::new(0, 0, 80, 24);
+/// let mut buf = Buffer::empty(area);
+/// ExitOverlay::new(PseudoTerminal::new(&snapshot).render_cursor(false), 1).render(area, &mut buf);
+/// ```
+pub struct ExitOverlay<'a, S: ?Sized> {
+    pseudo_term: PseudoTerminal<'a, S>,
+    code: i32,
+    style: Style,
+}
+
+impl<'a, S: Screen + ?Sized> ExitOverlay<'a, S> {
+    /// Creates a new overlay over the terminal for the exit code of the process.
+    #[inline]
+    #[must_use]
+    pub const fn new(pseudo_term: PseudoTerminal<'a, S>, code: i32) -> Self {
+        Self {
+            pseudo_term,
+            code,
+            style: Style::new().add_modifier(Modifier::REVERSED),
+        }
+    }
+
+    /// Sets the style of the banner (default = reversed).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_term::{
+    ///     snapshot::{ExitOverlay, ScreenSnapshot},
+    ///     widget::PseudoTerminal,
+    /// };
+    ///
+    /// let parser = vt100::Parser::new(24, 80, 0);
+    /// let snapshot = ScreenSnapshot::new(parser.screen());
+    /// let overlay =
+    ///     ExitOverlay::new(PseudoTerminal::new(&snapshot), 0).style(Style::default().bg(Color::Red));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl<S: Screen + ?Sized> Widget for ExitOverlay<'_, S> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let inner_area = self.pseudo_term.inner_area(area).intersection(buf.area);
+        self.pseudo_term.render(area, buf);
+        if inner_area.is_empty() {
+            return;
+        }
+        let message = format!("Process exited with code {} — press any key", self.code);
+        // Pad the message with a space on either side
+        let width = (cell_width(&message) + 2).min(inner_area.width);
+        let banner = Rect::new(
+            inner_area.x + (inner_area.width - width) / 2,
+            inner_area.y + inner_area.height / 2,
+            width,
+            1,
+        );
+        buf.set_style(banner, self.style);
+        buf.set_stringn(
+            banner.x + 1,
+            banner.y,
+            &message,
+            usize::from(width.saturating_sub(2)),
+            self.style,
+        );
+    }
+}
+
+#[cfg(all(test, feature = "vt100"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_renders_like_screen() {
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        parser.process(include_bytes!("../test/typescript/simple_ls.typescript"));
+        let snapshot = ScreenSnapshot::new(parser.screen());
+        let area = Rect::new(0, 0, 80, 24);
+
+        let mut expected = Buffer::empty(area);
+        PseudoTerminal::new(parser.screen()).render(area, &mut expected);
+        drop(parser);
+
+        let mut buf = Buffer::empty(area);
+        PseudoTerminal::new(&snapshot.clone()).render(area, &mut buf);
+        assert_eq!(buf, expected);
+        assert_eq!(snapshot.size(), (24, 80));
+        assert!(snapshot.cell(24, 0).is_none());
+    }
+
+    #[test]
+    fn exit_overlay_over_snapshot() {
+        let mut parser = vt100::Parser::new(5, 50, 0);
+        parser.process(b"$ false");
+        let snapshot = ScreenSnapshot::new(parser.screen());
+        drop(parser);
+        let area = Rect::new(0, 0, 50, 5);
+        let mut buf = Buffer::empty(area);
+        ExitOverlay::new(PseudoTerminal::new(&snapshot).render_cursor(false), 1)
+            .render(area, &mut buf);
+
+        let row = |y: u16| (0..50).map(|x| buf[(x, y)].symbol()).collect::<String>();
+        assert_eq!(row(0).trim_end(), "$ false");
+        assert_eq!(row(2), "    Process exited with code 1 — press any key    ");
+        assert_eq!(buf[(3, 2)].modifier, Modifier::REVERSED);
+        assert_eq!(buf[(2, 2)].modifier, Modifier::empty());
+    }
+}
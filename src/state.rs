@@ -1,51 +1,814 @@
-use ratatui::{buffer::Buffer, layout::Rect};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+};
 
-use crate::widget::{Cell, PseudoTerminal, Screen};
+use crate::{
+    palette,
+    widget::{
+        cell_width, Cell, CursorOrder, CursorPlacement, FlipMode, HighlightLayer, InverseMode,
+        PseudoTerminal, Rotation, Screen,
+    },
+};
 
 /// Draw the [`Screen`] to the [`Buffer`],
 /// area is the designated area that the consumer provides
-pub fn handle<S: Screen>(term: &PseudoTerminal<S>, area: Rect, buf: &mut Buffer) {
-    let cols = area.width;
-    let rows = area.height;
-    let col_start = area.x;
-    let row_start = area.y;
-    let area_cols = area.width + area.x;
-    let area_rows = area.height + area.y;
+pub fn handle<S: Screen + ?Sized>(term: &PseudoTerminal<S>, area: Rect, buf: &mut Buffer) {
+    // Never write outside of the buffer, even if the consumer provides a larger area
+    let area = area.intersection(buf.area);
+    match term.rotation {
+        Rotation::None => draw(term, area, buf),
+        Rotation::Deg180 => {
+            draw(term, area, buf);
+            flip_area(area, buf, FlipMode::Both);
+        }
+        Rotation::Cw90 | Rotation::Ccw90 => {
+            // Draw upright into a scratch buffer with swapped dimensions, starting from the
+            // cells underneath so that keyed out cells keep them
+            let upright = Rect::new(0, 0, area.height, area.width);
+            let rotated = |x: u16, y: u16| match term.rotation {
+                Rotation::Cw90 => (area.x + area.width - 1 - y, area.y + x),
+                _ => (area.x + y, area.y + area.height - 1 - x),
+            };
+            let mut scratch = Buffer::empty(upright);
+            for (x, y) in upright.positions().map(|position| (position.x, position.y)) {
+                scratch[(x, y)] = buf[rotated(x, y)].clone();
+            }
+            draw(term, upright, &mut scratch);
+            for (x, y) in upright.positions().map(|position| (position.x, position.y)) {
+                buf[rotated(x, y)] = scratch[(x, y)].clone();
+            }
+        }
+    }
+}
+
+/// Draw the [`Screen`] upright to the area of the [`Buffer`]
+fn draw<S: Screen + ?Sized>(term: &PseudoTerminal<S>, area: Rect, buf: &mut Buffer) {
     let screen = term.screen();
+    let (row_step, col_step) = scale_steps(term, area);
+    let h_offset = h_offset(term, (row_step, col_step));
 
     // The [`Screen`] is made out of rows of cells
+    if (row_step, col_step) == (1, 1) {
+        draw_cells(term, area, buf);
+        if let Some(marker) = &term.truncation_marker {
+            draw_truncation_markers(term, area, buf, marker);
+        }
+        if term.extend_bg_to_width {
+            extend_backgrounds(term, area, buf);
+        }
+    } else {
+        draw_scaled(term, area, buf, (row_step, col_step));
+    }
+
+    if term.sub_row_offset > 0.0 {
+        draw_sub_row_offset(area, buf, term.sub_row_offset);
+    }
+
+    if term.ligature_hints {
+        merge_ligatures(area, buf);
+    }
+
+    if let Some(styles) = term.zebra {
+        draw_zebra(area, buf, styles);
+    }
+
+    if let Some(hook) = term.on_row.as_ref().filter(|_| !area.is_empty()) {
+        let mut hook = hook.borrow_mut();
+        for row in 0..area.height {
+            let start = buf.index_of(area.x, area.y + row);
+            hook(row, &buf.content[start..start + usize::from(area.width)]);
+        }
+    }
+
+    let cursor_below_selection = term.cursor.draw_order == CursorOrder::BelowSelection;
+    // Drawn right below the first selection layer, or above all layers without one
+    let mut cursor_pending = term.render_cursor && cursor_below_selection;
+    // The layers are ordered from bottom to top, every layer wins over the ones before
+    for layer in term.highlight_layers.order.into_iter().flatten() {
+        if layer == HighlightLayer::Selection && cursor_pending {
+            handle_cursor(term, area, buf);
+            cursor_pending = false;
+        }
+        match layer {
+            HighlightLayer::CursorColumn => {
+                if let Some(style) = term.cursor_column_style {
+                    let c_col = (cursor_cell(term).1 / col_step).checked_sub(h_offset);
+                    if let Some(c_col) = c_col.filter(|&c_col| c_col < area.width) {
+                        buf.set_style(Rect::new(area.x + c_col, area.y, 1, area.height), style);
+                    }
+                }
+            }
+            HighlightLayer::CursorLine => {
+                if let Some(style) = term.cursor_line_style {
+                    let c_row = cursor_cell(term).0 / row_step;
+                    if c_row < area.height {
+                        buf.set_style(Rect::new(area.x, area.y + c_row, area.width, 1), style);
+                    }
+                }
+            }
+            HighlightLayer::Selection => {
+                if let Some(bounds) = term.selection {
+                    draw_selection(
+                        area,
+                        buf,
+                        bounds,
+                        term.selection_style,
+                        (row_step, col_step, h_offset),
+                    );
+                }
+            }
+        }
+    }
+    if cursor_pending {
+        handle_cursor(term, area, buf);
+    }
+
+    if term.debug_grid {
+        draw_debug_grid(area, buf);
+    }
+
+    if term.debug_scroll_region {
+        let (top, bottom) = screen.scroll_region();
+        draw_scroll_region(area, buf, (top / row_step, bottom / row_step));
+    }
+
+    if term.render_cursor && !cursor_below_selection {
+        handle_cursor(term, area, buf);
+    }
+
+    if let Some(preview) = term.input_preview.as_deref() {
+        draw_input_preview(term, area, buf, preview, (row_step, col_step));
+    }
+
+    if let Some(flip) = term.flip {
+        flip_area(area, buf, flip);
+    }
+}
+
+/// Draw the dimmed preview from the cursor on, the cursor is drawn over its first cell
+fn draw_input_preview<S: Screen + ?Sized>(
+    term: &PseudoTerminal<S>,
+    area: Rect,
+    buf: &mut Buffer,
+    preview: &str,
+    steps: (u16, u16),
+) {
+    let area = area.intersection(buf.area);
+    if steps != (1, 1) || preview.is_empty() {
+        return;
+    }
+    let (c_row, c_col) = cursor_cell(term);
+    let Some(col) = c_col.checked_sub(term.h_offset) else {
+        return;
+    };
+    if c_row >= area.height || col >= area.width {
+        return;
+    }
+    let (x, y) = (area.x + col, area.y + c_row);
+    let style = Style::new().add_modifier(Modifier::DIM);
+    buf.set_stringn(x, y, preview, usize::from(area.right() - x), style);
+    if term.render_cursor && cursor_position(term, area).is_some() {
+        buf[(x, y)].set_style(term.cursor.overlay_style);
+    }
+}
+
+/// Alternate the style of even and odd rows, for cells without a background color
+fn draw_zebra(area: Rect, buf: &mut Buffer, (even, odd): (Style, Style)) {
+    for (y, style) in (area.top()..area.bottom()).zip([even, odd].into_iter().cycle()) {
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            if cell.bg == Color::Reset {
+                cell.set_style(style);
+            }
+        }
+    }
+}
+
+/// Blend the foreground of the top rows towards the background, the top row the most
+pub fn fade_top(area: Rect, buf: &mut Buffer, rows: u16, background: (u8, u8, u8)) {
+    let rows = rows.min(area.height);
     for row in 0..rows {
-        for col in 0..cols {
-            let buf_col = col + col_start;
-            let buf_row = row + row_start;
+        let opacity = f32::from(row + 1) / f32::from(rows + 1);
+        let y = area.y + row;
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            if cell.fg == Color::Reset {
+                // The default foreground of the host is unknown
+                cell.modifier.insert(Modifier::DIM);
+            } else {
+                let background = palette::rgb(cell.bg).unwrap_or(background);
+                cell.fg = palette::blend(cell.fg, background, opacity);
+            }
+        }
+    }
+}
+
+/// Style the cells between the (row, column) bounds of the selection, like selected text
+fn draw_selection(
+    area: Rect,
+    buf: &mut Buffer,
+    [start, end]: [(u16, u16); 2],
+    style: Style,
+    (row_step, col_step, h_offset): (u16, u16, u16),
+) {
+    let (start, end) = (start.min(end), start.max(end));
+    for row in 0..area.height {
+        for col in 0..area.width {
+            let position = (row * row_step, (col + h_offset) * col_step);
+            if (start..=end).contains(&position) {
+                buf[(area.x + col, area.y + row)].set_style(style);
+            }
+        }
+    }
+}
+
+/// Mirror the cells of the area along the axes of the flip mode
+fn flip_area(area: Rect, buf: &mut Buffer, flip: FlipMode) {
+    let cells: Vec<_> = area.positions().map(|pos| buf[pos].clone()).collect();
+    for (pos, cell) in area.positions().zip(cells) {
+        buf[flipped(area, (pos.x, pos.y), flip)] = cell;
+    }
+}
+
+/// The (x, y) position a cell of the area is moved to, when the area is mirrored
+fn flipped(area: Rect, (x, y): (u16, u16), flip: FlipMode) -> (u16, u16) {
+    let horizontal = matches!(flip, FlipMode::Horizontal | FlipMode::Both);
+    let vertical = matches!(flip, FlipMode::Vertical | FlipMode::Both);
+    let x = if horizontal {
+        area.right() - 1 - (x - area.x)
+    } else {
+        x
+    };
+    let y = if vertical {
+        area.bottom() - 1 - (y - area.y)
+    } else {
+        y
+    };
+    (x, y)
+}
+
+/// The (x, y) position the cursor ends up at in the area, after flipping and rotating
+pub(crate) fn frame_cursor_position<S: Screen + ?Sized>(
+    term: &PseudoTerminal<S>,
+    area: Rect,
+) -> Option<(u16, u16)> {
+    let upright = match term.rotation {
+        Rotation::Cw90 | Rotation::Ccw90 => Rect::new(0, 0, area.height, area.width),
+        Rotation::None | Rotation::Deg180 => area,
+    };
+    let (_, mut position) = cursor_position(term, upright)?;
+    if let Some(flip) = term.flip {
+        position = flipped(upright, position, flip);
+    }
+    let (x, y) = position;
+    Some(match term.rotation {
+        Rotation::None => position,
+        Rotation::Deg180 => flipped(area, position, FlipMode::Both),
+        Rotation::Cw90 => (area.x + area.width - 1 - y, area.y + x),
+        Rotation::Ccw90 => (area.x + y, area.y + area.height - 1 - x),
+    })
+}
+
+/// Draw only the cursor of the [`Screen`] to the [`Buffer`],
+/// area is the designated area that the consumer provides
+pub fn handle_cursor<S: Screen + ?Sized>(term: &PseudoTerminal<S>, area: Rect, buf: &mut Buffer) {
+    let area = area.intersection(buf.area);
+    let screen = term.screen();
+
+    if let Some(((c_row, c_col), (x, y))) = cursor_position(term, area) {
+        let c_cell = &mut buf[(x, y)];
+        if let Some(cell) = screen.cell(c_row, c_col) {
+            let overlay = match term.cursor.placement {
+                CursorPlacement::Auto => cell.has_contents(),
+                CursorPlacement::Symbol => false,
+                CursorPlacement::Overlay => true,
+            };
+            if overlay {
+                let style = term.cursor.overlay_style;
+                c_cell.set_style(style);
+            } else {
+                let symbol = match term.cursor.bar_width {
+                    Some(width) => bar_glyph(width),
+                    None => &term.cursor.symbol,
+                };
+                let style = term.cursor.style;
+                c_cell.set_symbol(symbol);
+                c_cell.set_style(style);
+            }
+        }
+    }
+}
+
+/// The (row, column) of the cursor on the screen, honoring the position override
+pub(crate) fn cursor_cell<S: Screen + ?Sized>(term: &PseudoTerminal<S>) -> (u16, u16) {
+    term.cursor
+        .position_override
+        .unwrap_or_else(|| term.screen().cursor_position())
+}
+
+/// The position of the cursor on the screen and in the area, `None` if it is hidden or out of view
+pub(crate) fn cursor_position<S: Screen + ?Sized>(
+    term: &PseudoTerminal<S>,
+    area: Rect,
+) -> Option<((u16, u16), (u16, u16))> {
+    let screen = term.screen();
+    if !term.cursor.is_visible(screen.hide_cursor()) {
+        return None;
+    }
+    let (c_row, c_col) = cursor_cell(term);
+    let steps @ (row_step, col_step) = scale_steps(term, area);
+    let buf_row = c_row / row_step;
+    // A cursor left of the horizontal offset is out of view
+    let buf_col = (c_col / col_step).checked_sub(h_offset(term, steps))?;
+    // Compare against the dimensions instead of the absolute edges, so that a cursor
+    // on the last column (e.g. after a wide character) can't overflow past `area.right()`
+    (buf_row < area.height && buf_col < area.width)
+        .then_some(((c_row, c_col), (area.x + buf_col, area.y + buf_row)))
+}
+
+/// The left block glyph of a bar, that is `width` eighths of a cell wide
+fn bar_glyph(width: u16) -> &'static str {
+    const GLYPHS: [&str; 8] = ["▏", "▎", "▍", "▌", "▋", "▊", "▉", "█"];
+    GLYPHS[usize::from(width.clamp(1, 8)) - 1]
+}
+
+/// Fill the columns right of the screen with the background of the last cell of every row
+fn extend_backgrounds<S: Screen + ?Sized>(term: &PseudoTerminal<S>, area: Rect, buf: &mut Buffer) {
+    let area = area.intersection(buf.area);
+    let rows = term.screen().size().0.min(area.height);
+    let visible = term.screen().size().1.saturating_sub(term.h_offset);
+    if visible == 0 || visible >= area.width {
+        return;
+    }
+    for y in area.y..area.y + rows {
+        let bg = buf[(area.x + visible - 1, y)].bg;
+        if bg != Color::Reset {
+            for x in area.x + visible..area.right() {
+                buf[(x, y)].set_bg(bg);
+            }
+        }
+    }
+}
+
+/// Replace the last column of every row, that has content right of the area, with the marker
+fn draw_truncation_markers<S: Screen + ?Sized>(
+    term: &PseudoTerminal<S>,
+    area: Rect,
+    buf: &mut Buffer,
+    marker: &str,
+) {
+    let screen = term.screen();
+    let cols = screen.size().1;
+    let hidden = term.h_offset.saturating_add(area.width)..cols;
+    if area.is_empty() || hidden.is_empty() {
+        return;
+    }
+    for row in 0..area.height {
+        if hidden
+            .clone()
+            .any(|col| screen.cell(row, col).is_some_and(Cell::has_contents))
+        {
+            buf[(area.right() - 1, area.y + row)].set_symbol(marker);
+        }
+    }
+}
 
-            if buf_row > area_rows || buf_col > area_cols {
-                // Skip writing outside the area
+/// Replace the first row by lower blocks, that are `1 - offset` of a row high
+fn draw_sub_row_offset(area: Rect, buf: &mut Buffer, offset: f32) {
+    const GLYPHS: [&str; 9] = [" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+    // The rounded offset is in 0.0..=8.0
+    let eighths = ((1.0 - offset) * 8.0).round() as usize;
+    if area.is_empty() || eighths == 8 {
+        return;
+    }
+    for x in area.left()..area.right() {
+        let cell = &mut buf[(x, area.y)];
+        let color = if cell.symbol().trim().is_empty() {
+            cell.bg
+        } else {
+            cell.fg
+        };
+        let glyph = if color == Color::Reset {
+            " "
+        } else {
+            GLYPHS[eighths]
+        };
+        cell.set_symbol(glyph)
+            .set_fg(color)
+            .set_bg(Color::Reset)
+            .modifier = Modifier::empty();
+    }
+}
+
+/// Draw the cells of the [`Screen`] one to one
+///
+/// Runs of printable ASCII cells that share a style are batched into a single write,
+/// every other cell (e.g. wide characters) breaks the run and is written on its own.
+fn draw_cells<S: Screen + ?Sized>(term: &PseudoTerminal<S>, area: Rect, buf: &mut Buffer) {
+    let screen = term.screen();
+    let mut run = Run {
+        x: area.x,
+        style: Style::default(),
+        symbols: String::with_capacity(area.width.into()),
+    };
+
+    for row in 0..area.height {
+        let buf_row = area.y + row;
+        for col in 0..area.width {
+            let buf_col = area.x + col;
+            let cell = screen
+                .cell(row, col.saturating_add(term.h_offset))
+                .and_then(|screen_cell| buf_cell(term, screen_cell, &buf[(buf_col, buf_row)]));
+            let Some(cell) = cell else {
+                // Keep the underlying cell
+                run.flush(term, buf, buf_row);
                 continue;
+            };
+            if matches!(cell.symbol().as_bytes(), [b' '..=b'~']) {
+                let style = cell.style();
+                if run.symbols.is_empty() || run.style != style {
+                    run.flush(term, buf, buf_row);
+                    run.x = buf_col;
+                    run.style = style;
+                }
+                run.symbols.push_str(cell.symbol());
+            } else {
+                run.flush(term, buf, buf_row);
+                let mut cell = cell;
+                // A wide character, that doesn't fit, would spill over the edge of the area
+                if col + cell_width(cell.symbol()) > area.width {
+                    cell.set_symbol(" ");
+                }
+                buf[(buf_col, buf_row)] = cell;
+            }
+        }
+        run.flush(term, buf, buf_row);
+    }
+}
+
+/// Consecutive cells of a row that share the same style
+struct Run {
+    x: u16,
+    style: Style,
+    symbols: String,
+}
+
+impl Run {
+    /// Write the cells of the run to the given row of the buffer and start a new run
+    fn flush<S: Screen + ?Sized>(&mut self, term: &PseudoTerminal<S>, buf: &mut Buffer, y: u16) {
+        if self.symbols.is_empty() {
+            return;
+        }
+        // Every symbol of a run is a single byte, that takes up a single cell
+        let width = cell_width(&self.symbols);
+        if !term.clears_area() {
+            // Drop the flags of the underlying cells, like a fresh cell would
+            for x in self.x..self.x + width {
+                buf[(x, y)].reset();
+            }
+        }
+        // Start from a reset style, so that no modifier of the underlying buffer bleeds through
+        let style = Style::reset().patch(self.style);
+        buf.set_stringn(self.x, y, &self.symbols, width.into(), style);
+        self.symbols.clear();
+    }
+}
+
+/// Redraw a single cell of the [`Screen`] at (row, column) to the [`Buffer`],
+/// area is the designated area that the consumer provides
+pub fn handle_cell<S: Screen + ?Sized>(
+    term: &PseudoTerminal<S>,
+    (row, col): (u16, u16),
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let area = area.intersection(buf.area);
+    if let Some(buf_col) = col.checked_sub(term.h_offset) {
+        if row < area.height && buf_col < area.width {
+            if let Some(screen_cell) = term.screen().cell(row, col) {
+                let position = (area.x + buf_col, area.y + row);
+                if let Some(cell) = buf_cell(term, screen_cell, &buf[position]) {
+                    buf[position] = cell;
+                }
             }
+        }
+    }
+}
+
+/// The buffer cell a screen cell is drawn as over the `under` cell, `None` if it is keyed out
+/// as transparent
+fn buf_cell<S: Screen + ?Sized>(
+    term: &PseudoTerminal<S>,
+    screen_cell: &S::C,
+    under: &ratatui::buffer::Cell,
+) -> Option<ratatui::buffer::Cell> {
+    // The area may not have been cleared, start from a fresh cell so that
+    // no symbol or modifier of the underlying buffer bleeds through
+    let mut cell = fresh_cell(term, screen_cell);
+    if term.transparent_color == Some(cell.bg) {
+        return None;
+    }
+    if !term.transparent_colors.is_empty() {
+        let bg_keyed = is_keyed(&term.transparent_colors, cell.bg);
+        let fg_keyed = is_keyed(&term.transparent_colors, cell.fg);
+        if bg_keyed && (fg_keyed || cell.symbol().trim().is_empty()) {
+            return None;
+        }
+        if bg_keyed {
+            cell.bg = under.bg;
+        }
+        if fg_keyed {
+            cell.fg = under.fg;
+        }
+    }
+    if let Some(style) = term.protected_style {
+        if screen_cell.protected() {
+            cell.set_style(style);
+        }
+    }
+    Some(cell)
+}
+
+/// Whether the color is one of the keys, named and indexed colors match their RGB value
+fn is_keyed(keys: &[Color], color: Color) -> bool {
+    let rgb = palette::rgb(color);
+    keys.iter()
+        .any(|&key| key == color || rgb.is_some_and(|rgb| palette::rgb(key) == Some(rgb)))
+}
 
-            if let Some(screen_cell) = screen.cell(row, col) {
-                let cell = &mut buf[(buf_col, buf_row)];
-                screen_cell.apply(cell);
+/// Apply the screen cell to a default buffer cell, substituting mapped and unsupported glyphs
+fn fresh_cell<S: Screen + ?Sized>(
+    term: &PseudoTerminal<S>,
+    screen_cell: &S::C,
+) -> ratatui::buffer::Cell {
+    let mut cell = ratatui::buffer::Cell::default();
+    screen_cell.apply(&mut cell);
+    cell.fg = term.palette.resolve(cell.fg);
+    cell.bg = term.palette.resolve(cell.bg);
+    apply_base_style(term.base_style, &mut cell);
+    // The cells of a reset area start from the clear style, below the base style
+    if let Some(style) = term.clear_style.filter(|_| term.clears_area()) {
+        apply_base_style(style, &mut cell);
+    }
+    if term.inverse_mode == InverseMode::SwapColors && cell.modifier.contains(Modifier::REVERSED) {
+        swap_colors(term, &mut cell);
+    }
+    if term.opacity < 1.0 {
+        let background = term.blend_background();
+        cell.fg = palette::blend(cell.fg, background, term.opacity);
+        cell.bg = palette::blend(cell.bg, background, term.opacity);
+    }
+    if !term.symbol_map.is_empty() {
+        let mut chars = cell.symbol().chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            if let Some(&(_, to)) = term.symbol_map.iter().find(|(from, _)| *from == c) {
+                cell.set_symbol(to.encode_utf8(&mut [0; 4]));
+            }
+        }
+    }
+    if let Some(fallback) = &term.glyph_fallback {
+        if !cell.symbol().is_ascii() {
+            if let Some(glyph) = fallback(cell.symbol()) {
+                cell.set_symbol(&glyph);
             }
         }
     }
+    cell
+}
+
+/// Fill in the default colors of the cell from the base style and add its modifiers
+fn apply_base_style(base: Style, cell: &mut ratatui::buffer::Cell) {
+    if cell.fg == Color::Reset {
+        cell.fg = base.fg.unwrap_or(Color::Reset);
+    }
+    if cell.bg == Color::Reset {
+        cell.bg = base.bg.unwrap_or(Color::Reset);
+    }
+    cell.modifier.insert(base.add_modifier);
+}
+
+/// Render an inverse cell with swapped colors instead of the reversed modifier
+fn swap_colors<S: Screen + ?Sized>(term: &PseudoTerminal<S>, cell: &mut ratatui::buffer::Cell) {
+    // The default colors of the host are unknown, fall back to the style of the widget
+    let style = term.style.unwrap_or_default();
+    let fg = match cell.fg {
+        Color::Reset => style.fg.unwrap_or(Color::Gray),
+        fg => fg,
+    };
+    let bg = match cell.bg {
+        Color::Reset => style.bg.unwrap_or(Color::Black),
+        bg => bg,
+    };
+    cell.fg = bg;
+    cell.bg = fg;
+    cell.modifier.remove(Modifier::REVERSED);
+}
+
+/// The cells of the bottom row of the [`Screen`], as they would be drawn.
+pub fn live_line<S: Screen + ?Sized>(term: &PseudoTerminal<S>) -> Vec<ratatui::buffer::Cell> {
+    let screen = term.screen();
+    let (rows, cols) = screen.size();
+    (0..cols)
+        .map(|col| {
+            screen
+                .cell(rows.saturating_sub(1), col)
+                .map(|screen_cell| fresh_cell(term, screen_cell))
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// The number of (rows, columns) of the [`Screen`] that are sampled into a single cell.
+///
+/// This is only larger than one, if scaling is enabled and the screen doesn't fit the area.
+fn scale_steps<S: Screen + ?Sized>(term: &PseudoTerminal<S>, area: Rect) -> (u16, u16) {
+    if !term.scale_to_fit || area.is_empty() {
+        return (1, 1);
+    }
+    let (rows, cols) = term.screen().size();
+    (
+        rows.div_ceil(area.height).max(1),
+        cols.div_ceil(area.width).max(1),
+    )
+}
+
+/// The number of columns the view is panned to the right.
+///
+/// Panning is ignored, while the screen is downscaled.
+fn h_offset<S: Screen + ?Sized>(term: &PseudoTerminal<S>, steps: (u16, u16)) -> u16 {
+    if steps == (1, 1) {
+        term.h_offset
+    } else {
+        0
+    }
+}
+
+/// Draw a downscaled preview of the [`Screen`].
+///
+/// Every cell of the preview represents a block of cells of the screen and shows its
+/// most frequent symbol, or the most frequent background if the block is blank.
+fn draw_scaled<S: Screen + ?Sized>(
+    term: &PseudoTerminal<S>,
+    area: Rect,
+    buf: &mut Buffer,
+    (row_step, col_step): (u16, u16),
+) {
+    let screen = term.screen();
+    let (rows, cols) = screen.size();
+    let mut samples = Vec::with_capacity(usize::from(row_step) * usize::from(col_step));
 
-    if !screen.hide_cursor() && term.cursor.show {
-        let (c_row, c_col) = screen.cursor_position();
-        if (c_row + row_start) < area_rows && (c_col + col_start) < area_cols {
-            let c_cell = &mut buf[(c_col + col_start, c_row + row_start)];
-            if let Some(cell) = screen.cell(c_row, c_col) {
-                if cell.has_contents() {
-                    let style = term.cursor.overlay_style;
-                    c_cell.set_style(style);
-                } else {
-                    let symbol = &term.cursor.symbol;
-                    let style = term.cursor.style;
-                    c_cell.set_symbol(symbol);
-                    c_cell.set_style(style);
+    for row in 0..rows.div_ceil(row_step).min(area.height) {
+        for col in 0..cols.div_ceil(col_step).min(area.width) {
+            samples.clear();
+            for s_row in row * row_step..((row + 1) * row_step).min(rows) {
+                for s_col in col * col_step..((col + 1) * col_step).min(cols) {
+                    if let Some(screen_cell) = screen.cell(s_row, s_col) {
+                        samples.push(fresh_cell(term, screen_cell));
+                    }
                 }
             }
+            let filled = samples.iter().filter(|sample| sample.symbol() != " ");
+            let dominant = most_frequent(filled, ratatui::buffer::Cell::symbol)
+                .or_else(|| most_frequent(samples.iter(), |sample| sample.bg));
+            if let Some(dominant) = dominant {
+                buf[(area.x + col, area.y + row)] = dominant.clone();
+            }
+        }
+    }
+}
+
+/// The first of the most frequent cells, compared by the given key
+fn most_frequent<'c, K: PartialEq>(
+    cells: impl Iterator<Item = &'c ratatui::buffer::Cell>,
+    key: impl Fn(&'c ratatui::buffer::Cell) -> K,
+) -> Option<&'c ratatui::buffer::Cell> {
+    let mut counts: Vec<(K, usize, &ratatui::buffer::Cell)> = Vec::new();
+    for cell in cells {
+        let k = key(cell);
+        match counts.iter_mut().find(|(other, ..)| *other == k) {
+            Some((_, count, _)) => *count += 1,
+            None => counts.push((k, 1, cell)),
+        }
+    }
+    let mut dominant: Option<(usize, &ratatui::buffer::Cell)> = None;
+    for (_, count, cell) in counts {
+        if !matches!(dominant, Some((max, _)) if count <= max) {
+            dominant = Some((count, cell));
+        }
+    }
+    dominant.map(|(_, cell)| cell)
+}
+
+/// Pairs of symbols that common programming fonts render as a single ligature.
+const LIGATURES: &[&str] = &[
+    "!=", "==", "<=", ">=", "->", "<-", "=>", "::", "&&", "||", "//", "..", "++", "--",
+];
+
+/// Merge adjacent cells that form a ligature and share the same style into the first cell.
+///
+/// The second cell is left blank, the merged symbol is two columns wide and covers it.
+fn merge_ligatures(area: Rect, buf: &mut Buffer) {
+    for row in area.top()..area.bottom() {
+        let mut col = area.left();
+        while col + 1 < area.right() {
+            let (first, second) = (&buf[(col, row)], &buf[(col + 1, row)]);
+            let ligature = LIGATURES
+                .iter()
+                .find(|pair| pair.strip_prefix(first.symbol()) == Some(second.symbol()));
+            if let Some(ligature) = ligature.filter(|_| first.style() == second.style()) {
+                buf[(col, row)].set_symbol(ligature);
+                buf[(col + 1, row)].set_symbol(" ");
+                // A merged cell can't be the start of another pair
+                col += 2;
+            } else {
+                col += 1;
+            }
+        }
+    }
+}
+
+/// Distance in cells between two rulers of the debug grid.
+const DEBUG_GRID_SPACING: u16 = 10;
+
+/// Overlay faint rulers every [`DEBUG_GRID_SPACING`] rows and columns of the area.
+///
+/// Blank cells on a ruler get a ruler glyph, cells with contents are only dimmed.
+fn draw_debug_grid(area: Rect, buf: &mut Buffer) {
+    let style = Style::default().add_modifier(Modifier::DIM);
+    for row in 0..area.height {
+        let on_row = row > 0 && row % DEBUG_GRID_SPACING == 0;
+        for col in 0..area.width {
+            let on_col = col > 0 && col % DEBUG_GRID_SPACING == 0;
+            let symbol = match (on_row, on_col) {
+                (true, true) => "┼",
+                (true, false) => "┈",
+                (false, true) => "┊",
+                (false, false) => continue,
+            };
+            let cell = &mut buf[(area.x + col, area.y + row)];
+            if cell.symbol() == " " {
+                cell.set_symbol(symbol);
+            }
+            cell.set_style(style);
+        }
+    }
+}
+
+/// Mark the rows of the scroll region, from top to bottom inclusive, on the last column.
+fn draw_scroll_region(area: Rect, buf: &mut Buffer, (top, bottom): (u16, u16)) {
+    if area.is_empty() {
+        return;
+    }
+    let style = Style::default().add_modifier(Modifier::DIM);
+    let col = area.right() - 1;
+    for row in top..=bottom.min(area.height - 1) {
+        let symbol = match row {
+            _ if row == top => "┐",
+            _ if row == bottom => "┘",
+            _ => "│",
+        };
+        let cell = &mut buf[(col, area.y + row)];
+        if cell.symbol() == " " {
+            cell.set_symbol(symbol);
+        }
+        cell.set_style(style);
+    }
+}
+
+#[cfg(all(test, feature = "vt100"))]
+mod tests {
+    use ratatui::widgets::{Clear, Widget};
+
+    use super::*;
+
+    /// Render every cell on its own, as a reference for the batched rendering
+    fn render_per_cell(term: &PseudoTerminal<vt100::Screen>, area: Rect) -> Buffer {
+        let mut buf = Buffer::empty(area);
+        Clear.render(area, &mut buf);
+        for row in 0..area.height {
+            for col in 0..area.width {
+                handle_cell(term, (row, col), area, &mut buf);
+            }
+        }
+        buf
+    }
+
+    #[test]
+    fn batched_runs_match_per_cell() {
+        let area = Rect::new(0, 0, 80, 24);
+        let mut ls = vt100::Parser::new(24, 80, 0);
+        ls.process(include_bytes!("../test/typescript/simple_ls.typescript"));
+        let mut mixed = vt100::Parser::new(24, 80, 0);
+        mixed.process("ab\x1b[1mcd\x1b[0m好e\x1b[31;44mfg\x1b[0m é~\r\n好好x".as_bytes());
+
+        for parser in [&ls, &mixed] {
+            let term = PseudoTerminal::new(parser.screen()).render_cursor(false);
+            let expected = render_per_cell(&term, area);
+            let mut buf = Buffer::empty(area);
+            term.render(area, &mut buf);
+            assert_eq!(buf, expected);
         }
     }
 }
@@ -0,0 +1,50 @@
+//! Recommended environment for programs running in a [`PseudoTerminal`].
+//!
+//! Programs decide which sequences to emit based on `TERM` and `COLORTERM`, so they should be
+//! set when spawning the process, e.g. through `portable_pty::CommandBuilder::env`.
+//!
+//! With the `vt100` backend, the widget supports:
+//!
+//! - 16, 256 and truecolor foreground and background colors
+//! - bold, italic, underline and inverse attributes
+//! - the alternate screen, application cursor and keypad modes
+//! - mouse reporting and bracketed paste
+//!
+//! Not supported are e.g. dim, blinking and struck through text, hyperlinks and images.
+//! Programs degrade gracefully when they emit those anyway.
+//!
+//! [`PseudoTerminal`]: crate::widget::PseudoTerminal
+
+/// The `TERM` to announce to programs.
+pub const TERM: &str = "xterm-256color";
+
+/// The `COLORTERM` to announce to programs, which enables truecolor output.
+pub const COLORTERM: &str = "truecolor";
+
+/// Returns the environment variables to pass to the program, as (key, value) pairs.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::terminfo::recommended_env;
+///
+/// let mut cmd = std::process::Command::new("ls");
+/// cmd.envs(recommended_env());
+/// ```
+#[inline]
+#[must_use]
+pub fn recommended_env() -> Vec<(&'static str, &'static str)> {
+    vec![("TERM", TERM), ("COLORTERM", COLORTERM)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recommended_env_enables_truecolor() {
+        let env = recommended_env();
+        assert!(env.contains(&("COLORTERM", "truecolor")));
+        assert!(env.contains(&("TERM", "xterm-256color")));
+    }
+}
@@ -19,6 +19,31 @@ impl Screen for vt100::Screen {
     fn cursor_position(&self) -> (u16, u16) {
         self.cursor_position()
     }
+
+    #[inline]
+    fn size(&self) -> (u16, u16) {
+        self.size()
+    }
+
+    #[inline]
+    fn row_wrapped(&self, row: u16) -> bool {
+        self.row_wrapped(row)
+    }
+
+    #[inline]
+    fn application_cursor(&self) -> bool {
+        self.application_cursor()
+    }
+
+    #[inline]
+    fn application_keypad(&self) -> bool {
+        self.application_keypad()
+    }
+
+    #[inline]
+    fn title(&self) -> &str {
+        self.title()
+    }
 }
 
 impl Cell for vt100::Cell {
@@ -31,6 +56,21 @@ impl Cell for vt100::Cell {
     fn apply(&self, cell: &mut ratatui::buffer::Cell) {
         fill_buf_cell(self, cell)
     }
+
+    #[inline]
+    fn contents(&self) -> String {
+        self.contents()
+    }
+
+    #[inline]
+    fn fg(&self) -> ratatui::style::Color {
+        Color::from(self.fgcolor()).into()
+    }
+
+    #[inline]
+    fn bg(&self) -> ratatui::style::Color {
+        Color::from(self.bgcolor()).into()
+    }
 }
 
 #[inline]
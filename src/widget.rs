@@ -1,19 +1,29 @@
+use std::cell::RefCell;
+
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
+    layout::{Constraint, Position, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Clear, Widget},
+    text::{Line, Span, Text},
+    widgets::{Block, Clear, Row, Table, Widget},
+    Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
-use crate::state;
+use crate::{export, export::TextExportOptions, palette, state};
 
 /// A trait representing a pseudo-terminal screen.
 ///
 /// Implementing this trait allows for backends other than `vt100` to be used
 /// with the `PseudoTerminal` widget.
+///
+/// Only [`cell`](Screen::cell), [`hide_cursor`](Screen::hide_cursor) and
+/// [`cursor_position`](Screen::cursor_position) are required. The other methods default to
+/// a terminal without the respective mode or state, backends that don't track it can keep
+/// the default.
 pub trait Screen {
     /// The type of cell this screen contains
-    type C: Cell;
+    type C: Cell + ?Sized;
 
     /// Returns the cell at the given location if it exists.
     fn cell(&self, row: u16, col: u16) -> Option<&Self::C>;
@@ -23,14 +33,548 @@ pub trait Screen {
     ///
     /// The return value is expected to be (row, column)
     fn cursor_position(&self) -> (u16, u16);
+    /// Returns the size of the screen.
+    ///
+    /// The return value is expected to be (rows, columns).
+    /// The default implementation probes [`Screen::cell`] along the first row and column.
+    fn size(&self) -> (u16, u16) {
+        let rows = (0..u16::MAX)
+            .find(|&row| self.cell(row, 0).is_none())
+            .unwrap_or(u16::MAX);
+        let cols = (0..u16::MAX)
+            .find(|&col| self.cell(0, col).is_none())
+            .unwrap_or(u16::MAX);
+        (rows, cols)
+    }
+    /// Returns whether the given row was soft-wrapped into the next row.
+    ///
+    /// Wrapping is tracked per row rather than per cell: if a row is wrapped, its last cell
+    /// continues on the first cell of the next row.
+    #[inline]
+    fn row_wrapped(&self, _row: u16) -> bool {
+        false
+    }
+    /// Returns whether the program enabled focus reporting (`\x1b[?1004h`).
+    ///
+    /// If enabled, focus changes of the host window should be forwarded,
+    /// see [`input::focus_event`](crate::input::focus_event).
+    ///
+    /// `vt100` doesn't expose the mode and always returns `false`. Programs enabling it are
+    /// reported by [`SequenceLog`](crate::diagnostics::SequenceLog), or track the mode from
+    /// the output and pass it to [`input::focus_event`](crate::input::focus_event).
+    #[inline]
+    fn focus_reporting(&self) -> bool {
+        false
+    }
+    /// Returns the scroll region (DECSTBM) of the screen.
+    ///
+    /// The return value is expected to be (top, bottom), both rows are inclusive.
+    /// The default spans the whole screen.
+    ///
+    /// `vt100` scrolls within the region, but doesn't expose it and always returns the
+    /// default.
+    #[inline]
+    fn scroll_region(&self) -> (u16, u16) {
+        let (rows, _) = self.size();
+        (0, rows.saturating_sub(1))
+    }
+    /// Returns the cursor position saved through DECSC (`\x1b7`), if any.
+    ///
+    /// The return value is expected to be (row, column).
+    ///
+    /// `vt100` restores the saved cursor on DECRC (`\x1b8`), but doesn't expose it and always
+    /// returns `None`.
+    #[inline]
+    fn saved_cursor(&self) -> Option<(u16, u16)> {
+        None
+    }
+    /// Returns whether the program enabled alternate scroll mode (`\x1b[?1007h`).
+    ///
+    /// If enabled while the alternate screen is active, the mouse wheel should send arrow keys,
+    /// see [`input::alt_scroll_keys`](crate::input::alt_scroll_keys).
+    ///
+    /// `vt100` doesn't expose the mode and always returns `false`. Programs enabling it are
+    /// reported by [`SequenceLog`](crate::diagnostics::SequenceLog), or track the mode from
+    /// the output and pass it to [`input::alt_scroll_keys`](crate::input::alt_scroll_keys).
+    #[inline]
+    fn alt_scroll(&self) -> bool {
+        false
+    }
+    /// Returns whether origin mode (DECOM, `\x1b[?6h`) is set.
+    ///
+    /// In origin mode, rows are addressed relative to the top of the
+    /// [scroll region](Screen::scroll_region).
+    ///
+    /// `vt100` addresses rows in origin mode, but doesn't expose it and always returns `false`.
+    #[inline]
+    fn origin_mode(&self) -> bool {
+        false
+    }
+    /// Returns whether insert mode (IRM, `\x1b[4h`) is active.
+    ///
+    /// In insert mode, printed characters shift the rest of the line to the right instead of
+    /// overwriting it.
+    ///
+    /// `vt100` doesn't support insert mode and always returns `false`. Programs enabling it are
+    /// reported by [`SequenceLog`](crate::diagnostics::SequenceLog).
+    #[inline]
+    fn insert_mode(&self) -> bool {
+        false
+    }
+    /// Returns whether echo is disabled, e.g. while a password prompt reads input.
+    ///
+    /// Echo is a setting of the pty, not an escape sequence, so parsers like `vt100` can't
+    /// track it and always return `false`. Query the pty instead, e.g. through `tcgetattr`, and
+    /// set [`PseudoTerminal::mask_input`]. Backends that do track it get the lock of
+    /// [`PseudoTerminal::mask_input`] in the top right corner while echo is disabled.
+    #[inline]
+    fn echo_disabled(&self) -> bool {
+        false
+    }
+    /// Returns whether the program enabled application cursor keys (DECCKM, `\x1b[?1h`).
+    ///
+    /// If enabled, the cursor keys should be sent SS3 prefixed,
+    /// see [`input::keypad_key_sequence`](crate::input::keypad_key_sequence).
+    #[inline]
+    fn application_cursor(&self) -> bool {
+        false
+    }
+    /// Returns whether the program enabled the application keypad (DECKPAM, `\x1b=`).
+    ///
+    /// If enabled, the keys of the numeric keypad should be sent SS3 prefixed,
+    /// see [`input::keypad_key_sequence`](crate::input::keypad_key_sequence).
+    #[inline]
+    fn application_keypad(&self) -> bool {
+        false
+    }
+    /// Returns the window title set by the program, e.g. through `\x1b]2;title\x07`, empty
+    /// by default.
+    #[inline]
+    fn title(&self) -> &str {
+        ""
+    }
+}
+
+/// Declares [`DynScreen`] with one `dyn_` method per forwarded [`Screen`] method, implements
+/// it for every [`Screen`] with owned cells and implements [`Screen`] for `dyn DynScreen`.
+macro_rules! dyn_screen {
+    ($(
+        $(#[$doc:meta])*
+        fn $dyn_name:ident => $name:ident(&self $(, $arg:ident: $arg_ty:ty)*) -> $ret:ty;
+    )*) => {
+        /// An object-safe facade over [`Screen`].
+        ///
+        /// The associated cell type of [`Screen`] prevents it from being used as a trait
+        /// object. `DynScreen` erases the cell type, so that screens of different backends can
+        /// be stored uniformly, e.g. in a `Vec<Box<dyn DynScreen>>`, and rendered through
+        /// [`PseudoTerminal::from_dyn`].
+        ///
+        /// It is implemented for every [`Screen`] with owned cells and does not need to be
+        /// implemented manually. Every method forwards to the [`Screen`] method of the same
+        /// name without the `dyn_` prefix.
+        pub trait DynScreen {
+            /// Returns the cell at the given location if it exists.
+            fn dyn_cell(&self, row: u16, col: u16) -> Option<&(dyn Cell + 'static)>;
+            $(
+                $(#[$doc])*
+                fn $dyn_name(&self $(, $arg: $arg_ty)*) -> $ret;
+            )*
+        }
+
+        impl<S> DynScreen for S
+        where
+            S: Screen,
+            S::C: Sized + 'static,
+        {
+            #[inline]
+            fn dyn_cell(&self, row: u16, col: u16) -> Option<&(dyn Cell + 'static)> {
+                self.cell(row, col).map(|cell| cell as _)
+            }
+
+            $(
+                #[inline]
+                fn $dyn_name(&self $(, $arg: $arg_ty)*) -> $ret {
+                    self.$name($($arg),*)
+                }
+            )*
+        }
+
+        impl Screen for dyn DynScreen + '_ {
+            type C = dyn Cell;
+
+            #[inline]
+            fn cell(&self, row: u16, col: u16) -> Option<&Self::C> {
+                self.dyn_cell(row, col)
+            }
+
+            $(
+                #[inline]
+                fn $name(&self $(, $arg: $arg_ty)*) -> $ret {
+                    self.$dyn_name($($arg),*)
+                }
+            )*
+        }
+    };
+}
+
+dyn_screen! {
+    /// Returns whether the terminal should be hidden
+    fn dyn_hide_cursor => hide_cursor(&self) -> bool;
+    /// Returns cursor position of screen.
+    ///
+    /// The return value is expected to be (row, column)
+    fn dyn_cursor_position => cursor_position(&self) -> (u16, u16);
+    /// Returns the size of the screen.
+    ///
+    /// The return value is expected to be (rows, columns)
+    fn dyn_size => size(&self) -> (u16, u16);
+    /// Returns whether the given row was soft-wrapped into the next row.
+    fn dyn_row_wrapped => row_wrapped(&self, row: u16) -> bool;
+    /// Returns whether the program enabled focus reporting.
+    fn dyn_focus_reporting => focus_reporting(&self) -> bool;
+    /// Returns the scroll region (DECSTBM) of the screen.
+    fn dyn_scroll_region => scroll_region(&self) -> (u16, u16);
+    /// Returns the cursor position saved through DECSC, if any.
+    fn dyn_saved_cursor => saved_cursor(&self) -> Option<(u16, u16)>;
+    /// Returns whether the program enabled alternate scroll mode.
+    fn dyn_alt_scroll => alt_scroll(&self) -> bool;
+    /// Returns whether origin mode is set.
+    fn dyn_origin_mode => origin_mode(&self) -> bool;
+    /// Returns whether insert mode is active.
+    fn dyn_insert_mode => insert_mode(&self) -> bool;
+    /// Returns whether the program enabled application cursor keys.
+    fn dyn_application_cursor => application_cursor(&self) -> bool;
+    /// Returns whether the program enabled the application keypad.
+    fn dyn_application_keypad => application_keypad(&self) -> bool;
+    /// Returns whether echo is disabled.
+    fn dyn_echo_disabled => echo_disabled(&self) -> bool;
+    /// Returns the window title set by the program.
+    fn dyn_title => title(&self) -> &str;
 }
 
 /// A trait for representing a single cell on a screen.
+///
+/// Only [`has_contents`](Cell::has_contents) and [`apply`](Cell::apply) are required, the
+/// other methods are derived from them or default to a plain cell.
 pub trait Cell {
     /// Whether the cell has any contents that could be rendered to the screen.
     fn has_contents(&self) -> bool;
     /// Apply the contents and styling of this cell to the provided buffer cell.
     fn apply(&self, cell: &mut ratatui::buffer::Cell);
+    /// The text of the cell, empty if it has no contents, e.g. a blank cell or the
+    /// continuation cell of a wide character.
+    ///
+    /// The default applies the cell to a scratch buffer cell, backends should return their
+    /// contents directly.
+    #[inline]
+    fn contents(&self) -> String {
+        if !self.has_contents() {
+            return String::new();
+        }
+        let mut scratch = ratatui::buffer::Cell::default();
+        self.apply(&mut scratch);
+        scratch.symbol().to_string()
+    }
+    /// The foreground color of the cell, [`Color::Reset`] for the default color.
+    ///
+    /// This allows analyzing the colors of a screen without rendering it. The default applies
+    /// the cell to a scratch buffer cell, backends should return the color directly.
+    /// `vt100` reports the 16 named ANSI colors as [`Color::Indexed`] `0..16`, e.g. red as
+    /// `Indexed(1)` rather than [`Color::Red`].
+    #[inline]
+    fn fg(&self) -> Color {
+        let mut scratch = ratatui::buffer::Cell::default();
+        self.apply(&mut scratch);
+        scratch.fg
+    }
+    /// The background color of the cell, [`Color::Reset`] for the default color.
+    ///
+    /// The default applies the cell to a scratch buffer cell, backends should return the
+    /// color directly.
+    #[inline]
+    fn bg(&self) -> Color {
+        let mut scratch = ratatui::buffer::Cell::default();
+        self.apply(&mut scratch);
+        scratch.bg
+    }
+    /// Whether the cell is protected from erasure, e.g. through the DECSCA attribute.
+    #[inline]
+    fn protected(&self) -> bool {
+        false
+    }
+    /// The character set that was active when the contents of the cell were written,
+    /// [`Charset::Ascii`] by default.
+    ///
+    /// `vt100` doesn't expose the character set of its cells, so they always return
+    /// [`Charset::Ascii`] and exports leave their contents as they are, whatever
+    /// [`LinedrawMode`](crate::export::LinedrawMode) is set.
+    #[inline]
+    fn charset(&self) -> Charset {
+        Charset::Ascii
+    }
+}
+
+/// The number of columns the contents of a cell take up on the screen.
+///
+/// Wide characters, e.g. CJK or emoji, take up two columns, the continuation cell after them
+/// has no contents. All helpers of this crate count columns with this function.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::widget::cell_width;
+///
+/// assert_eq!(cell_width("a"), 1);
+/// assert_eq!(cell_width("字"), 2);
+/// assert_eq!(cell_width(""), 0);
+/// ```
+#[inline]
+#[must_use]
+pub fn cell_width(content: &str) -> u16 {
+    u16::try_from(UnicodeWidthStr::width(content)).unwrap_or(u16::MAX)
+}
+
+/// The character set a cell was written with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Charset {
+    /// The regular character set
+    #[default]
+    Ascii,
+    /// The DEC special graphics character set, that maps ASCII letters to line drawing glyphs
+    DecSpecialGraphics,
+}
+
+/// The axes a [`PseudoTerminal`] is mirrored along, see [`PseudoTerminal::flip`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlipMode {
+    /// Mirror left and right
+    Horizontal,
+    /// Mirror top and bottom
+    Vertical,
+    /// Mirror along both axes, which rotates the screen by 180 degrees
+    Both,
+}
+
+/// How inverse cells (SGR 7) are rendered, see [`PseudoTerminal::inverse_mode`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InverseMode {
+    /// Keep the colors and set [`Modifier::REVERSED`], the host terminal swaps them
+    #[default]
+    Modifier,
+    /// Swap the foreground and background colors of the cell
+    SwapColors,
+}
+
+/// A theme for the 16 ANSI colors, see [`PseudoTerminal::palette`].
+///
+/// The 8 normal colors (`Indexed(0..8)`, e.g. [`Color::Red`]) and the 8 bright colors
+/// (`Indexed(8..16)`, e.g. [`Color::LightRed`]) have their own slots, so the bright colors can
+/// be themed independently. Colors without a slot are drawn as the program set them.
+///
+/// # Example
+///
+/// ```
+/// use ratatui::style::Color;
+/// use tui_term::widget::Palette;
+///
+/// // Red and bright red of the Dracula theme
+/// let palette = Palette::new()
+///     .normal(1, Color::Rgb(255, 85, 85))
+///     .bright(1, Color::Rgb(255, 110, 110));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Palette {
+    colors: [Option<Color>; 16],
+}
+
+impl Palette {
+    /// Creates a palette without any slot set.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { colors: [None; 16] }
+    }
+
+    /// Sets the normal color `index`, from `0` (black) to `7` (white).
+    ///
+    /// Indices outside of `0..8` are ignored.
+    #[inline]
+    #[must_use]
+    pub fn normal(mut self, index: u8, color: Color) -> Self {
+        if index < 8 {
+            self.colors[usize::from(index)] = Some(color);
+        }
+        self
+    }
+
+    /// Sets the bright color `index`, from `0` (bright black) to `7` (bright white).
+    ///
+    /// Indices outside of `0..8` are ignored.
+    #[inline]
+    #[must_use]
+    pub fn bright(mut self, index: u8, color: Color) -> Self {
+        if index < 8 {
+            self.colors[usize::from(index) + 8] = Some(color);
+        }
+        self
+    }
+
+    /// The color the palette maps the color to.
+    #[inline]
+    pub(crate) fn resolve(&self, color: Color) -> Color {
+        palette::ansi_index(color)
+            .and_then(|index| self.colors[usize::from(index)])
+            .unwrap_or(color)
+    }
+}
+
+/// The orientation a [`PseudoTerminal`] is rendered in, see [`PseudoTerminal::rotate`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rotation {
+    /// Upright
+    #[default]
+    None,
+    /// Rotated by 90 degrees clockwise, the first row becomes the last column
+    Cw90,
+    /// Rotated by 90 degrees counterclockwise, the first row becomes the first column
+    Ccw90,
+    /// Upside down
+    Deg180,
+}
+
+/// Whether the cursor or the selection wins where they overlap, see [`Cursor::draw_order`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorOrder {
+    /// Draw the cursor above the content and every highlight
+    #[default]
+    AboveContent,
+    /// Draw the selection above the cursor
+    BelowSelection,
+}
+
+/// How the cursor is drawn over a cell, see [`Cursor::placement`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorPlacement {
+    /// Draw the [symbol](Cursor::symbol) over blank cells and the
+    /// [overlay style](Cursor::overlay_style) over cells with contents
+    #[default]
+    Auto,
+    /// Always replace the cell with the symbol, hiding its contents
+    Symbol,
+    /// Always apply the overlay style, keeping the cell as it is, e.g. for a thin symbol like
+    /// `"|"`, that looks odd next to spaces
+    Overlay,
+}
+
+/// A highlight drawn over the content, see [`HighlightLayers`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HighlightLayer {
+    /// The [cursor column](PseudoTerminal::cursor_column_style)
+    CursorColumn,
+    /// The [cursor line](PseudoTerminal::cursor_line_style)
+    CursorLine,
+    /// The [selection](PseudoTerminal::selection)
+    Selection,
+}
+
+/// The order the highlights are drawn in, where they overlap the top layer wins.
+///
+/// By default the cursor column is at the bottom, followed by the cursor line and the
+/// selection on top. The cursor is drawn above all of them, unless it is configured through
+/// [`Cursor::draw_order`] to be drawn right below the selection. Then it is drawn below the
+/// first selection layer, or above all layers if the order has none.
+///
+/// # Example
+///
+/// ```
+/// use tui_term::widget::{HighlightLayer, HighlightLayers};
+///
+/// // Keep the cursor line visible within a selection
+/// let layers = HighlightLayers::new([
+///     HighlightLayer::CursorColumn,
+///     HighlightLayer::Selection,
+///     HighlightLayer::CursorLine,
+/// ]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HighlightLayers {
+    pub(crate) order: [Option<HighlightLayer>; 3],
+}
+
+impl HighlightLayers {
+    /// Creates the order of the layers, from bottom to top.
+    ///
+    /// A layer listed more than once is only drawn at its topmost position.
+    #[inline]
+    #[must_use]
+    pub const fn new(order: [HighlightLayer; 3]) -> Self {
+        let mut layers = [None; 3];
+        let mut i = 0;
+        while i < order.len() {
+            let mut above = i + 1;
+            while above < order.len() && order[above] as u8 != order[i] as u8 {
+                above += 1;
+            }
+            if above == order.len() {
+                layers[i] = Some(order[i]);
+            }
+            i += 1;
+        }
+        Self { order: layers }
+    }
+}
+
+impl Default for HighlightLayers {
+    #[inline]
+    fn default() -> Self {
+        Self::new([
+            HighlightLayer::CursorColumn,
+            HighlightLayer::CursorLine,
+            HighlightLayer::Selection,
+        ])
+    }
+}
+
+/// What was clicked, see [`PseudoTerminal::handle_click`].
+///
+/// The bounds are in screen coordinates, `x` being the column and `y` the row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClickAction {
+    /// A URL, e.g. to open it in the browser
+    Link {
+        /// The URL
+        url: String,
+        /// The cells of the URL on the clicked row
+        bounds: Rect,
+    },
+    /// A word, e.g. to select or copy it
+    Word {
+        /// The text of the word
+        text: String,
+        /// The cells of the word
+        bounds: Rect,
+    },
+    /// A blank cell
+    Empty {
+        /// The row of the cell
+        row: u16,
+        /// The column of the cell
+        col: u16,
+    },
+}
+
+/// A corner of the inner area of a [`PseudoTerminal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Corner {
+    /// The first column of the first row
+    TopLeft,
+    /// The last column of the first row
+    TopRight,
+    /// The first column of the last row
+    BottomLeft,
+    /// The last column of the last row
+    BottomRight,
 }
 
 /// A widget representing a pseudo-terminal screen.
@@ -65,19 +609,100 @@ pub trait Cell {
 ///     );
 /// ```
 #[non_exhaustive]
-pub struct PseudoTerminal<'a, S> {
+pub struct PseudoTerminal<'a, S: ?Sized> {
     screen: &'a S,
     pub(crate) block: Option<Block<'a>>,
-    style: Option<Style>,
+    pub(crate) style: Option<Style>,
     pub(crate) cursor: Cursor,
+    pub(crate) debug_grid: bool,
+    pub(crate) debug_scroll_region: bool,
+    pub(crate) render_cursor: bool,
+    pub(crate) transparent_color: Option<Color>,
+    pub(crate) transparent_colors: Vec<Color>,
+    pub(crate) protected_style: Option<Style>,
+    pub(crate) base_style: Style,
+    pub(crate) cursor_line_style: Option<Style>,
+    pub(crate) cursor_column_style: Option<Style>,
+    pub(crate) scale_to_fit: bool,
+    reset_area_first: bool,
+    pub(crate) clear_style: Option<Style>,
+    pub(crate) ligature_hints: bool,
+    shadow: Option<Style>,
+    pub(crate) h_offset: u16,
+    pub(crate) glyph_fallback: Option<GlyphFallback<'a>>,
+    pub(crate) symbol_map: &'a [(char, char)],
+    new_output_indicator: Option<&'a str>,
+    peek_live_line: Option<Style>,
+    scroll_fade: Option<u16>,
+    scroll_percentage_label: Option<Corner>,
+    corner_statuses: Vec<(Corner, Line<'a>)>,
+    pub(crate) opacity: f32,
+    pub(crate) flip: Option<FlipMode>,
+    pub(crate) rotation: Rotation,
+    pub(crate) selection: Option<[(u16, u16); 2]>,
+    pub(crate) selection_style: Style,
+    pub(crate) highlight_layers: HighlightLayers,
+    max_cols: Option<u16>,
+    trim_trailing_blank_rows: bool,
+    pub(crate) zebra: Option<(Style, Style)>,
+    pub(crate) inverse_mode: InverseMode,
+    clip_mask: Option<ClipMask<'a>>,
+    auto_title: bool,
+    title_style_fn: Option<TitleStyle<'a>>,
+    pub(crate) on_row: Option<RefCell<RowHook<'a>>>,
+    tab_width: Option<u16>,
+    pub(crate) truncation_marker: Option<String>,
+    pub(crate) sub_row_offset: f32,
+    pub(crate) input_preview: Option<String>,
+    cell_aspect: f32,
+    frozen: bool,
+    static_render: bool,
+    mask_input: Option<Corner>,
+    pub(crate) extend_bg_to_width: bool,
+    letterbox: Option<Style>,
+    pub(crate) palette: Palette,
+    overlays: Vec<Overlay<'a>>,
 }
 
+/// Substitutes a glyph, that the host can't display.
+type GlyphFallback<'a> = Box<dyn Fn(&str) -> Option<String> + 'a>;
+
+/// Decides for every (x, y) position of the buffer, whether it may be drawn to.
+type ClipMask<'a> = Box<dyn Fn(u16, u16) -> bool + 'a>;
+
+/// Styles the title, that the program set.
+type TitleStyle<'a> = Box<dyn Fn(&str) -> Style + 'a>;
+
+/// Called with every drawn row, see [`PseudoTerminal::on_row`].
+type RowHook<'a> = Box<dyn FnMut(u16, &[CellInfo]) + 'a>;
+
+/// A cell as it was drawn to the buffer, passed to [`PseudoTerminal::on_row`].
+pub type CellInfo = ratatui::buffer::Cell;
+
+/// A widget that is rendered above the terminal, with its area in screen coordinates.
+type Overlay<'a> = (Rect, Box<dyn FnOnce(Rect, &mut Buffer) + 'a>);
+
+/// The cursor configuration of a [`PseudoTerminal`].
+///
+/// Whether the cursor is drawn is decided in the following order:
+///
+/// 1. If the cursor is hidden through [`Cursor::visibility`] or [`Cursor::hide`], it is never
+///    drawn.
+/// 2. If the screen requests the cursor to be hidden (e.g. through `\x1b[?25l`), it is not drawn,
+///    unless [`Cursor::force_show`] is set.
+/// 3. Otherwise the cursor is drawn.
+#[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct Cursor {
     pub(crate) show: bool,
+    pub(crate) force_show: bool,
     pub(crate) symbol: String,
     pub(crate) style: Style,
     pub(crate) overlay_style: Style,
+    pub(crate) bar_width: Option<u16>,
+    pub(crate) position_override: Option<(u16, u16)>,
+    pub(crate) draw_order: CursorOrder,
+    pub(crate) placement: CursorPlacement,
 }
 
 impl Cursor {
@@ -146,6 +771,81 @@ impl Cursor {
         self
     }
 
+    /// Draws the cursor as a bar of the given width in eighths of a cell (default = `None`).
+    ///
+    /// The bar is drawn with a left block glyph, from `▏` (1) to `█` (8), instead of the
+    /// [symbol](Cursor::symbol). Widths are clamped to that range.
+    /// Like the symbol, the bar is only drawn on cells without contents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::Cursor;
+    ///
+    /// let cursor = Cursor::default().bar_width(2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn bar_width(mut self, bar_width: u16) -> Self {
+        self.bar_width = Some(bar_width);
+        self
+    }
+
+    /// Draws the cursor at the given (row, column) of the screen, instead of the cursor
+    /// position of the screen (default = `None`).
+    ///
+    /// This allows a ghost cursor or a preview, e.g. in split-editing views.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::Cursor;
+    ///
+    /// let cursor = Cursor::default().position_override(Some((2, 5)));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn position_override(mut self, position: Option<(u16, u16)>) -> Self {
+        self.position_override = position;
+        self
+    }
+
+    /// Sets whether the cursor is drawn above or below the
+    /// [selection](PseudoTerminal::selection) (default = [`CursorOrder::AboveContent`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::{Cursor, CursorOrder};
+    ///
+    /// let cursor = Cursor::default().draw_order(CursorOrder::BelowSelection);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn draw_order(mut self, draw_order: CursorOrder) -> Self {
+        self.draw_order = draw_order;
+        self
+    }
+
+    /// Sets whether the cursor replaces the cell with its symbol or applies the overlay style
+    /// (default = [`CursorPlacement::Auto`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::{Cursor, CursorPlacement};
+    ///
+    /// let cursor = Cursor::default()
+    ///     .symbol("|")
+    ///     .placement(CursorPlacement::Overlay);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn placement(mut self, placement: CursorPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
     /// Set the visibility of the cursor (default = shown)
     #[inline]
     #[must_use]
@@ -154,6 +854,23 @@ impl Cursor {
         self
     }
 
+    /// Show the cursor even if the screen requests it to be hidden (default = false)
+    ///
+    /// This does not override hiding the cursor through [`Cursor::visibility`] or
+    /// [`Cursor::hide`].
+    #[inline]
+    #[must_use]
+    pub const fn force_show(mut self, force_show: bool) -> Self {
+        self.force_show = force_show;
+        self
+    }
+
+    /// Whether the cursor should be drawn, given the screen's request to hide it.
+    #[inline]
+    pub(crate) const fn is_visible(&self, screen_hides_cursor: bool) -> bool {
+        self.show && (self.force_show || !screen_hides_cursor)
+    }
+
     /// Show the cursor (default)
     #[inline]
     pub fn show(&mut self) {
@@ -172,14 +889,19 @@ impl Default for Cursor {
     fn default() -> Self {
         Self {
             show: true,
+            force_show: false,
             symbol: "\u{2588}".into(), //"█".
             style: Style::default().fg(Color::Gray),
             overlay_style: Style::default().add_modifier(Modifier::REVERSED),
+            bar_width: None,
+            position_override: None,
+            draw_order: CursorOrder::AboveContent,
+            placement: CursorPlacement::Auto,
         }
     }
 }
 
-impl<'a, S: Screen> PseudoTerminal<'a, S> {
+impl<'a, S: Screen + ?Sized> PseudoTerminal<'a, S> {
     /// Creates a new instance of `PseudoTerminal`.
     ///
     /// # Arguments
@@ -203,6 +925,54 @@ impl<'a, S: Screen> PseudoTerminal<'a, S> {
             block: None,
             style: None,
             cursor: Cursor::default(),
+            debug_grid: false,
+            debug_scroll_region: false,
+            render_cursor: true,
+            transparent_color: None,
+            transparent_colors: Vec::new(),
+            protected_style: None,
+            base_style: Style::new(),
+            cursor_line_style: None,
+            cursor_column_style: None,
+            scale_to_fit: false,
+            reset_area_first: true,
+            clear_style: None,
+            ligature_hints: false,
+            shadow: None,
+            h_offset: 0,
+            glyph_fallback: None,
+            symbol_map: &[],
+            new_output_indicator: None,
+            peek_live_line: None,
+            scroll_fade: None,
+            scroll_percentage_label: None,
+            corner_statuses: Vec::new(),
+            opacity: 1.0,
+            flip: None,
+            rotation: Rotation::None,
+            selection: None,
+            selection_style: Style::new(),
+            highlight_layers: HighlightLayers::default(),
+            max_cols: None,
+            trim_trailing_blank_rows: false,
+            zebra: None,
+            inverse_mode: InverseMode::Modifier,
+            clip_mask: None,
+            auto_title: false,
+            title_style_fn: None,
+            on_row: None,
+            tab_width: None,
+            truncation_marker: None,
+            sub_row_offset: 0.0,
+            input_preview: None,
+            cell_aspect: 1.0,
+            frozen: false,
+            static_render: false,
+            mask_input: None,
+            extend_bg_to_width: false,
+            letterbox: None,
+            palette: Palette::new(),
+            overlays: Vec::new(),
         }
     }
 
@@ -230,6 +1000,63 @@ impl<'a, S: Screen> PseudoTerminal<'a, S> {
         self
     }
 
+    /// Adds the window title, that the program set, to the block (default = `false`).
+    ///
+    /// The title is only shown if a [block](PseudoTerminal::block) is set and the title isn't
+    /// empty, see [`Screen::title`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::widgets::{Block, Borders};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"\x1b]2;htop\x07");
+    /// let pseudo_term = PseudoTerminal::new(parser.screen())
+    ///     .block(Block::default().borders(Borders::ALL))
+    ///     .auto_title(true);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn auto_title(mut self, auto_title: bool) -> Self {
+        self.auto_title = auto_title;
+        self
+    }
+
+    /// Styles the [automatic title](PseudoTerminal::auto_title) depending on its text.
+    ///
+    /// The function is called with the title on every render, e.g. to highlight sessions
+    /// whose last command failed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::{
+    ///     style::{Color, Style},
+    ///     widgets::{Block, Borders},
+    /// };
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen())
+    ///     .block(Block::default().borders(Borders::ALL))
+    ///     .auto_title(true)
+    ///     .title_style_fn(|title| {
+    ///         if title.starts_with("[exit") {
+    ///             Style::default().fg(Color::Red)
+    ///         } else {
+    ///             Style::default()
+    ///         }
+    ///     });
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn title_style_fn(mut self, style: impl Fn(&str) -> Style + 'a) -> Self {
+        self.title_style_fn = Some(Box::new(style));
+        self
+    }
+
     /// Sets the cursor configuration for the `PseudoTerminal`.
     ///
     /// The `cursor` method allows configuring the appearance of the cursor within the
@@ -279,112 +1106,3936 @@ impl<'a, S: Screen> PseudoTerminal<'a, S> {
         self
     }
 
+    /// Overlays faint rulers every 10 rows and columns on top of the content.
+    ///
+    /// This is purely a diagnostic aid for layout and alignment issues and is off by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).debug_grid(true);
+    /// ```
     #[inline]
     #[must_use]
-    pub const fn screen(&self) -> &S {
+    pub const fn debug_grid(mut self, debug_grid: bool) -> Self {
+        self.debug_grid = debug_grid;
+        self
+    }
+
+    /// Marks the scroll region of the screen on the last column (default = false).
+    ///
+    /// The top and bottom margins reported by [`Screen::scroll_region`] are marked with `┐` and
+    /// `┘`, the rows in between with `│`. Like the debug grid, markers only replace blank
+    /// cells, cells with contents are dimmed. `vt100` screens always report the whole screen.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).debug_scroll_region(true);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn debug_scroll_region(mut self, debug_scroll_region: bool) -> Self {
+        self.debug_scroll_region = debug_scroll_region;
+        self
+    }
+
+    /// Sets whether the cursor is drawn together with the content (default = true).
+    ///
+    /// Disable this to draw the cursor in a separate pass with [`PseudoTerminal::draw_cursor`],
+    /// e.g. on an overlay layer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).render_cursor(false);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn render_cursor(mut self, render_cursor: bool) -> Self {
+        self.render_cursor = render_cursor;
+        self
+    }
+
+    /// Renders without anything that depends on time, e.g. for screenshots in docs and tests
+    /// (default = `false`).
+    ///
+    /// The cursor is always shown as the solid block, regardless of its
+    /// [symbol](Cursor::symbol), [bar width](Cursor::bar_width),
+    /// [placement](Cursor::placement) and whether the screen hides it. Blink modifiers are stripped
+    /// from every cell, so the output is reproducible.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).static_render(true);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn static_render(mut self, enabled: bool) -> Self {
+        self.static_render = enabled;
+        self
+    }
+
+    /// Shows a lock in a corner inside the block, e.g. while a password prompt doesn't echo
+    /// input (default = `None`).
+    ///
+    /// Set it while [echo is disabled](Screen::echo_disabled), `vt100` can't track echo, so
+    /// take it from the echo setting of the pty. Screens that report disabled echo get the
+    /// lock in the top right corner without it. The lock is drawn like a
+    /// [corner status](PseudoTerminal::corner_status), above the statuses of the same corner
+    /// and below the [scroll percentage label](PseudoTerminal::scroll_percentage_label), so
+    /// pick a corner that is free.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::{Corner, PseudoTerminal};
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let echo = false;
+    /// let pseudo_term =
+    ///     PseudoTerminal::new(parser.screen()).mask_input((!echo).then_some(Corner::TopRight));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn mask_input(mut self, corner: Option<Corner>) -> Self {
+        self.mask_input = corner;
+        self
+    }
+
+    /// Freezes the rendered frame, e.g. to pause scrolling output for inspection
+    /// (default = `false`).
+    ///
+    /// While frozen, the [`PseudoTerminalState`] keeps the frame of the first frozen render
+    /// and every following render redraws it, even as the screen changes underneath. The
+    /// frame is captured again, when the area changes or the state is
+    /// [invalidated](PseudoTerminalState::invalidate). Unfreezing drops it and shows the live
+    /// screen again. This has no effect, when the widget is rendered without a state.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+    /// use tui_term::widget::{PseudoTerminal, PseudoTerminalState};
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let mut state = PseudoTerminalState::new();
+    /// let area = Rect::new(0, 0, 80, 24);
+    /// let mut buf = Buffer::empty(area);
+    /// let paused = true;
+    /// PseudoTerminal::new(parser.screen())
+    ///     .frozen(paused)
+    ///     .render(area, &mut buf, &mut state);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn frozen(mut self, frozen: bool) -> Self {
+        self.frozen = frozen;
+        self
+    }
+
+    /// Sets a chroma-key color, cells with this background color are not drawn.
+    ///
+    /// This leaves the underlying buffer visible in place of those cells, which allows
+    /// compositing the terminal over another widget.
+    ///
+    /// When a transparent color is set, the area is not cleared before drawing.
+    /// Only the cells that are drawn are reset, everything else keeps the content of the
+    /// underlying buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::Color;
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).transparent_color(Color::Rgb(0, 255, 0));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn transparent_color(mut self, color: Color) -> Self {
+        self.transparent_color = Some(color);
+        self
+    }
+
+    /// Sets colors, that are transparent in the foreground and background of every cell
+    /// (default = none).
+    ///
+    /// Where the background is one of the colors, the background of the underlying buffer
+    /// shows through, the same goes for the foreground. Cells, whose background is
+    /// transparent and that show no text, are not drawn at all. This renders terminal art over
+    /// another widget, e.g. an image in the background.
+    ///
+    /// Named and indexed colors are resolved with the default xterm palette before they are
+    /// compared, so [`Color::Green`] also keys out `Indexed(2)` and `Rgb(0, 205, 0)`.
+    /// [`Color::Reset`] only matches itself. Colors are compared before the
+    /// [opacity](PseudoTerminal::opacity) is applied.
+    ///
+    /// Like with [`transparent_color`](PseudoTerminal::transparent_color), the area is not
+    /// cleared before drawing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::Color;
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term =
+    ///     PseudoTerminal::new(parser.screen()).transparent_colors(&[Color::Black, Color::Magenta]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn transparent_colors(mut self, colors: &[Color]) -> Self {
+        self.transparent_colors = colors.to_vec();
+        self
+    }
+
+    /// Sets the theme of the 16 ANSI colors, the program sets (default = [`Palette::new`]).
+    ///
+    /// The colors are mapped before the [base style](PseudoTerminal::base_style) is applied,
+    /// and before they are resolved to RGB, e.g. for the
+    /// [opacity](PseudoTerminal::opacity).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::Color;
+    /// use tui_term::widget::{Palette, PseudoTerminal};
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let palette = Palette::new().bright(1, Color::Rgb(255, 110, 110));
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).palette(palette);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Sets the style every cell starts from, before the attributes of the program are applied
+    /// (default = no style).
+    ///
+    /// This is the theme of the terminal: cells in the default colors take the colors of the base
+    /// style and its modifiers are added to every cell. The styles are layered from bottom to top:
+    ///
+    /// 1. the base style,
+    /// 2. the colors and attributes the program sets,
+    /// 3. the [protected style](Self::protected_style),
+    /// 4. the highlights, e.g. the [selection](Self::selection),
+    /// 5. the cursor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen())
+    ///     .base_style(Style::default().fg(Color::White).bg(Color::Rgb(40, 42, 54)));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn base_style(mut self, style: Style) -> Self {
+        self.base_style = style;
+        self
+    }
+
+    /// Sets a style that is overlaid on protected cells (default = `None`).
+    ///
+    /// This makes the boundaries of forms visible, that mark their fields as protected.
+    /// Without a style, protected cells are rendered like any other cell.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen())
+    ///     .protected_style(Some(Style::default().bg(Color::DarkGray)));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn protected_style(mut self, style: Option<Style>) -> Self {
+        self.protected_style = style;
+        self
+    }
+
+    /// Sets a style that is overlaid across the whole row the cursor is on (default = `None`).
+    ///
+    /// The highlight spans the full width of the inner area and is applied on top of the cell
+    /// styles (including the protected style), but below the cursor itself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen())
+    ///     .cursor_line_style(Some(Style::default().bg(Color::DarkGray)));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn cursor_line_style(mut self, style: Option<Style>) -> Self {
+        self.cursor_line_style = style;
+        self
+    }
+
+    /// Sets a style that is overlaid down the whole column the cursor is in (default = `None`).
+    ///
+    /// The highlight spans the full height of the inner area, which is handy for aligning
+    /// tabular output. Where it intersects the cursor line highlight
+    /// ([`PseudoTerminal::cursor_line_style`]), the line highlight is applied on top.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen())
+    ///     .cursor_column_style(Some(Style::default().bg(Color::DarkGray)));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn cursor_column_style(mut self, style: Option<Style>) -> Self {
+        self.cursor_column_style = style;
+        self
+    }
+
+    /// Downscales the screen to fit the area, if it is larger than the area (default = false).
+    ///
+    /// Every rendered cell then represents a block of screen cells, showing its most frequent
+    /// symbol, or its most frequent background if the block is blank.
+    /// This is lossy, but useful for minimaps and previews.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).scale_to_fit(true);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn scale_to_fit(mut self, scale_to_fit: bool) -> Self {
+        self.scale_to_fit = scale_to_fit;
+        self
+    }
+
+    /// Resets the whole area before drawing (default = true).
+    ///
+    /// The reset behaves like [`Clear`]: every cell of the area gets a blank symbol, the
+    /// default foreground, background and underline color, no modifiers and the skip flag is
+    /// unset. Cells outside of the area are never touched.
+    ///
+    /// Cells drawn from the screen always replace the symbol and the whole style of the
+    /// underlying cell, so no modifiers bleed into them, even without the reset.
+    /// Disabling the reset only keeps the cells of the area that the screen doesn't cover,
+    /// e.g. when the area is larger than the screen.
+    ///
+    /// When a [`transparent_color`](PseudoTerminal::transparent_color) is set, the area is never
+    /// reset, regardless of this setting.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).reset_area_first(false);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn reset_area_first(mut self, reset_area_first: bool) -> Self {
+        self.reset_area_first = reset_area_first;
+        self
+    }
+
+    /// Sets the style the area is filled with, when it is reset (default = `None`).
+    ///
+    /// Without a style, the reset leaves the cells in the default colors of the buffer. With a
+    /// style, e.g. the background of the terminal, every cell of the area matches it and
+    /// doesn't flash in a mismatched color. The cells of the screen keep the colors the
+    /// program set and the [`base_style`](PseudoTerminal::base_style), only their default
+    /// colors are taken from the style.
+    /// Nothing is filled, if the area isn't reset, see [`PseudoTerminal::reset_area_first`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term =
+    ///     PseudoTerminal::new(parser.screen()).clear_style(Style::default().bg(Color::Black));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn clear_style(mut self, style: Style) -> Self {
+        self.clear_style = Some(style);
+        self
+    }
+
+    /// Merges known ligature pairs like `!=` or `->` into a single cell (default = false).
+    ///
+    /// Ratatui renders every cell on its own, which breaks up programming ligatures.
+    /// With hints enabled, two adjacent cells that form a known pair and share the same style
+    /// are drawn as one symbol in the first cell, leaving the second cell blank.
+    /// This is best-effort, whether the ligature is shown depends on the font of the host.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).ligature_hints(true);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn ligature_hints(mut self, ligature_hints: bool) -> Self {
+        self.ligature_hints = ligature_hints;
+        self
+    }
+
+    /// Sets the style of a drop shadow, that is drawn to the bottom-right (default = `None`).
+    ///
+    /// The shadow is offset by one cell and stays inside the provided area,
+    /// so the terminal and its block shrink by one row and one column when it is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term =
+    ///     PseudoTerminal::new(parser.screen()).shadow(Some(Style::default().bg(Color::DarkGray)));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn shadow(mut self, style: Option<Style>) -> Self {
+        self.shadow = style;
+        self
+    }
+
+    /// Sets a fallback for glyphs the host terminal can't display.
+    ///
+    /// The fallback is called with the symbol of every non-ASCII cell, returning `Some`
+    /// replaces the symbol, `None` keeps it.
+    /// This enables ASCII-only rendering modes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).glyph_fallback(|glyph| match glyph {
+    ///     "─" => Some("-".to_string()),
+    ///     _ => None,
+    /// });
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn glyph_fallback(mut self, fallback: impl Fn(&str) -> Option<String> + 'a) -> Self {
+        self.glyph_fallback = Some(Box::new(fallback));
+        self
+    }
+
+    /// Sets a table of (from, to) characters, that are substituted in every cell
+    /// (default = empty).
+    ///
+    /// Unlike the [glyph fallback](PseudoTerminal::glyph_fallback), the table doesn't allocate,
+    /// which makes it suited for common substitutions, e.g. swapping heavy box drawing
+    /// characters for lighter ones. The first matching entry wins, the fallback sees the
+    /// substituted symbol.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).symbol_map(&[('━', '─'), ('┃', '│')]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn symbol_map(mut self, symbol_map: &'a [(char, char)]) -> Self {
+        self.symbol_map = symbol_map;
+        self
+    }
+
+    /// Sets a hook, that is called with every row of the screen after it was drawn.
+    ///
+    /// The hook receives the index of the row inside the area and its cells, as they were
+    /// drawn to the buffer before the cursor and any highlights.
+    /// This allows collecting statistics or per-row effects without scanning the buffer again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let mut blank_rows = 0;
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).on_row(|_, cells| {
+    ///     if cells.iter().all(|cell| cell.symbol() == " ") {
+    ///         blank_rows += 1;
+    ///     }
+    /// });
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn on_row(mut self, hook: impl FnMut(u16, &[CellInfo]) + 'a) -> Self {
+        self.on_row = Some(RefCell::new(Box::new(hook)));
+        self
+    }
+
+    /// Sets the tab width of the program, to restore tabs in text exports (default = `None`).
+    ///
+    /// Terminals expand tabs by moving the cursor, so the screen only contains blank cells.
+    /// With a tab width, [`PseudoTerminal::to_plain_text`] exports runs of at least two blank
+    /// cells, that end at a tab stop, as a tab. This keeps the alignment when the text is
+    /// pasted somewhere with the same tab width.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"name\tsize");
+    /// let text = PseudoTerminal::new(parser.screen())
+    ///     .tab_width(8)
+    ///     .to_plain_text();
+    /// assert_eq!(text, "name\tsize");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn tab_width(mut self, tab_width: u16) -> Self {
+        self.tab_width = Some(tab_width);
+        self
+    }
+
+    /// Centers the screen in a larger area and fills the bars around it with the style
+    /// (default = `None`).
+    ///
+    /// Without letterboxing, a screen smaller than the area is drawn in its top left corner.
+    /// This is meant for terminals of a fixed size in large panes. The bars are inside the
+    /// block, and positions, e.g. of [`PseudoTerminal::handle_click`], account for the offset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term =
+    ///     PseudoTerminal::new(parser.screen()).letterbox(Style::default().bg(Color::DarkGray));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn letterbox(mut self, style: Style) -> Self {
+        self.letterbox = Some(style);
+        self
+    }
+
+    /// Extends the background of every row to the right edge of the area (default = `false`).
+    ///
+    /// When the area is wider than the screen, the columns right of the screen are left
+    /// blank, so lines a program colored, e.g. with `\x1b[K`, stop at the edge of the screen.
+    /// With this enabled, those columns take the background of the last cell of their row.
+    /// Rows ending in the default background are left as they are.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"\x1b[44m status \x1b[K");
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).extend_bg_to_width(true);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn extend_bg_to_width(mut self, extend: bool) -> Self {
+        self.extend_bg_to_width = extend;
+        self
+    }
+
+    /// Sets a marker drawn in the last column of rows, that are cut off on the right
+    /// (default = `None`).
+    ///
+    /// A row is cut off, if the screen has content right of the area, e.g. when it is
+    /// narrower than the screen or panned. The marker takes the style of the cell it replaces.
+    /// It isn't drawn, while the screen is downscaled through
+    /// [`PseudoTerminal::scale_to_fit`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).truncation_marker(Some("…"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn truncation_marker(mut self, marker: Option<&str>) -> Self {
+        self.truncation_marker = marker.map(Into::into);
+        self
+    }
+
+    /// Restricts drawing to the buffer positions the mask returns `true` for.
+    ///
+    /// The mask is called with the (x, y) position of every cell of the area,
+    /// cells it returns `false` for are left untouched, including the block and the cursor.
+    /// This punches holes, e.g. for floating widgets, without cleaning up after rendering.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// // Keep a popup at the top right
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).clip_mask(|x, y| x < 60 || y >= 5);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn clip_mask(mut self, mask: impl Fn(u16, u16) -> bool + 'a) -> Self {
+        self.clip_mask = Some(Box::new(mask));
+        self
+    }
+
+    /// Sets an indicator, that new output arrived while the view is scrolled up
+    /// (default = `None`).
+    ///
+    /// The indicator is centered on the last row of the inner area. It is only shown when
+    /// rendered with a [`PseudoTerminalState`], that is not
+    /// [at the bottom](PseudoTerminalState::at_bottom) and was
+    /// [notified of new output](PseudoTerminalState::notify_new_output).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term =
+    ///     PseudoTerminal::new(parser.screen()).new_output_indicator(Some("↓ new output ↓"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn new_output_indicator(mut self, indicator: Option<&'a str>) -> Self {
+        self.new_output_indicator = indicator;
+        self
+    }
+
+    /// Peeks at the live bottom line of the screen, while the view is scrolled up
+    /// (default = `None`).
+    ///
+    /// The line is drawn over the last row of the inner area, patched with the given style,
+    /// e.g. [`Modifier::DIM`], so a new prompt isn't lost while reading the scrollback.
+    /// It is only shown when rendered with a [`PseudoTerminalState`], that is not
+    /// [at the bottom](PseudoTerminalState::at_bottom).
+    ///
+    /// A scrolled [`Screen`] only exposes the scrollback, so the live line is captured
+    /// whenever the view is rendered at the bottom, and the last capture is shown.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::{Modifier, Style};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 100);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen())
+    ///     .peek_live_line(Some(Style::default().add_modifier(Modifier::DIM)));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn peek_live_line(mut self, style: Option<Style>) -> Self {
+        self.peek_live_line = style;
+        self
+    }
+
+    /// Fades out the given number of top rows, while the view is scrolled up
+    /// (default = `None`).
+    ///
+    /// This hints, that there is more content above. The foreground of the top row is
+    /// blended the most towards the background, colors that depend on the host terminal are
+    /// dimmed instead.
+    /// It is only shown when rendered with a [`PseudoTerminalState`], that is not
+    /// [at the bottom](PseudoTerminalState::at_bottom).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 100);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).scroll_fade(Some(3));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn scroll_fade(mut self, rows: Option<u16>) -> Self {
+        self.scroll_fade = rows;
+        self
+    }
+
+    /// Shows how far the view is scrolled back, as a percentage in a corner inside the block
+    /// (default = `None`).
+    ///
+    /// The percentage is the [scroll offset](PseudoTerminalState::scroll_offset) relative to
+    /// the [scrollback length](PseudoTerminalState::set_scrollback_len), e.g. `"47%"`, `100%`
+    /// being the oldest row. It is rendered like a [corner status](PseudoTerminal::corner_status)
+    /// and only shown when rendered with a [`PseudoTerminalState`], that is not
+    /// [at the bottom](PseudoTerminalState::at_bottom).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::{Corner, PseudoTerminal};
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 100);
+    /// let pseudo_term =
+    ///     PseudoTerminal::new(parser.screen()).scroll_percentage_label(Some(Corner::TopRight));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn scroll_percentage_label(mut self, corner: Option<Corner>) -> Self {
+        self.scroll_percentage_label = corner;
+        self
+    }
+
+    /// Sets the opacity of the cells, from `0.0` to `1.0` (default = `1.0`).
+    ///
+    /// Foreground and background colors of every cell are blended towards the background of
+    /// the widget [style](PseudoTerminal::style), or black if it has none.
+    /// Named and indexed colors are resolved with the default xterm palette first,
+    /// the default colors ([`Color::Reset`]) depend on the host and are not blended.
+    /// Calling this on every frame of an animation loop fades the terminal in or out.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).opacity(0.5);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Shows a dimmed preview of the input, that is about to be sent, at the cursor
+    /// (default = `None`).
+    ///
+    /// This gives immediate feedback while the input isn't echoed by the program yet. The
+    /// screen isn't modified and the cursor stays where it is, it is drawn over the first
+    /// character of the preview. The preview is cut off at the right edge of the area and
+    /// isn't drawn while the screen is downscaled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"$ ");
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).input_preview(Some("git status"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn input_preview(mut self, preview: Option<&str>) -> Self {
+        self.input_preview = preview.map(Into::into);
+        self
+    }
+
+    /// Sets the height of a cell relative to its width, for previews (default = `1.0`).
+    ///
+    /// Terminal cells are about twice as tall as wide, so art drawn with cells looks stretched
+    /// when its dots are square, e.g. in the [Braille preview](PseudoTerminal::braille_preview).
+    /// With an aspect of e.g. `2.0`, every row is sampled twice, which keeps the proportions.
+    /// The aspect has no effect on the normal rendering, and neither on the downscaled
+    /// rendering through [`PseudoTerminal::scale_to_fit`], which draws cells of the same shape.
+    /// It is clamped to `0.1..=10.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).cell_aspect(2.0);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn cell_aspect(mut self, aspect: f32) -> Self {
+        self.cell_aspect = aspect.clamp(0.1, 10.0);
+        self
+    }
+
+    /// Shifts the content up by a fraction of a row, from `0.0` to `1.0` (default = `0.0`).
+    ///
+    /// This is a building block for smooth scroll animations: step the offset from `0.0`
+    /// towards `1.0` over a few frames, then scroll by a whole row and start again at `0.0`.
+    ///
+    /// Terminals can't draw text at a fraction of a cell, so the effect is lossy. Only the
+    /// first row, which is partly scrolled out, is drawn partially: every cell is replaced by
+    /// a lower block glyph in steps of an eighth of a row, in the color of its text, or of its
+    /// background if it is blank. The text of that row is lost. The other rows are drawn in
+    /// place, and the next row doesn't scroll in at the bottom edge.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).sub_row_offset(0.25);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn sub_row_offset(mut self, offset: f32) -> Self {
+        self.sub_row_offset = offset.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets how inverse cells are rendered (default = [`InverseMode::Modifier`]).
+    ///
+    /// With [`InverseMode::SwapColors`] the colors are swapped, so that downstream
+    /// compositing sees the actual colors. The default colors of the host terminal are unknown,
+    /// they are taken from the widget [style](PseudoTerminal::style), or swapped as
+    /// [`Color::Gray`] on [`Color::Black`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::{InverseMode, PseudoTerminal};
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).inverse_mode(InverseMode::SwapColors);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn inverse_mode(mut self, inverse_mode: InverseMode) -> Self {
+        self.inverse_mode = inverse_mode;
+        self
+    }
+
+    /// Mirrors the rendered screen along the given axes (default = not mirrored).
+    ///
+    /// This is meant for special displays, e.g. mirrored kiosk setups, and only affects the
+    /// placement of the cells, including the cursor. Glyphs themselves are not mirrored,
+    /// and wide characters end up behind their continuation cell when flipped horizontally.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::{FlipMode, PseudoTerminal};
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).flip(FlipMode::Horizontal);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn flip(mut self, flip: FlipMode) -> Self {
+        self.flip = Some(flip);
+        self
+    }
+
+    /// Highlights the text from `start` to `end`, both inclusive, like a selection
+    /// (default = no selection).
+    ///
+    /// Both bounds are (row, column) of the screen, in any order. Like selected text, the
+    /// selection runs to the end of the first row and starts at the beginning of the last.
+    /// It is drawn above the cursor line and column highlights, the cursor is drawn above it
+    /// unless configured otherwise through [`Cursor::draw_order`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).selection(
+    ///     (0, 4),
+    ///     (2, 10),
+    ///     Style::default().bg(Color::Blue),
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn selection(mut self, start: (u16, u16), end: (u16, u16), style: Style) -> Self {
+        self.selection = Some([start, end]);
+        self.selection_style = style;
+        self
+    }
+
+    /// Sets the order the highlights are drawn in (default = [`HighlightLayers::default`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::{HighlightLayer, HighlightLayers, PseudoTerminal};
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term =
+    ///     PseudoTerminal::new(parser.screen()).highlight_layers(HighlightLayers::new([
+    ///         HighlightLayer::Selection,
+    ///         HighlightLayer::CursorColumn,
+    ///         HighlightLayer::CursorLine,
+    ///     ]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn highlight_layers(mut self, layers: HighlightLayers) -> Self {
+        self.highlight_layers = layers;
+        self
+    }
+
+    /// Rotates the rendered screen, e.g. for pivoted displays (default = [`Rotation::None`]).
+    ///
+    /// For 90 degree rotations, rows and columns swap: the screen is drawn into an upright area
+    /// as high as the inner area is wide, so the screen should be sized to
+    /// (rows = area width, columns = area height).
+    /// Only the placement of the cells is rotated, including the cursor. Glyphs themselves
+    /// stay upright, wide characters take up two rows, and positions reported by
+    /// [`PseudoTerminalState`] are not rotated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::{PseudoTerminal, Rotation};
+    ///
+    /// let mut parser = vt100::Parser::new(80, 24, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).rotate(Rotation::Cw90);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn rotate(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Caps the number of rendered columns (default = `None`).
+    ///
+    /// If the inner area is wider, the screen is clipped at `max_cols` and the remaining
+    /// columns are left as padding, e.g. to keep a right margin for documentation that must
+    /// fit a fixed width. The block still spans the whole area.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).max_cols(72);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn max_cols(mut self, max_cols: u16) -> Self {
+        self.max_cols = Some(max_cols);
+        self
+    }
+
+    /// Skips the blank rows at the bottom of the screen (default = `false`).
+    ///
+    /// The area shrinks from the bottom to the last row with contents or the cursor,
+    /// the block included. The rows below are left untouched, e.g. for the next item of a
+    /// flex layout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).trim_trailing_blank_rows(true);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn trim_trailing_blank_rows(mut self, trim: bool) -> Self {
+        self.trim_trailing_blank_rows = trim;
+        self
+    }
+
+    /// Alternates the style of even and odd rows (default = `None`).
+    ///
+    /// The style only applies to cells without a background color, backgrounds set by the
+    /// program are kept. This is a display aid for dense output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).zebra(Some((
+    ///     Style::default(),
+    ///     Style::default().bg(Color::DarkGray),
+    /// )));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn zebra(mut self, styles: Option<(Style, Style)>) -> Self {
+        self.zebra = styles;
+        self
+    }
+
+    /// Adds a status, e.g. an elapsed time, to a corner inside the block.
+    ///
+    /// The status is rendered above the terminal content and the cursor, but below overlays.
+    /// It is truncated to the width of the inner area.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::{Corner, PseudoTerminal};
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).corner_status(Corner::TopRight, "00:42");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn corner_status(mut self, corner: Corner, status: impl Into<Line<'a>>) -> Self {
+        self.corner_statuses.push((corner, status.into()));
+        self
+    }
+
+    /// Shows the cursor position as `(row, col)` in a corner inside the block
+    /// (default = `None`).
+    ///
+    /// The coordinates are zero-based, as returned by [`Screen::cursor_position`].
+    /// They are rendered like a [corner status](PseudoTerminal::corner_status),
+    /// which helps to verify coordinate math, e.g. when building editors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::{Corner, PseudoTerminal};
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term =
+    ///     PseudoTerminal::new(parser.screen()).show_cursor_coords(Some(Corner::BottomRight));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn show_cursor_coords(self, corner: Option<Corner>) -> Self {
+        let Some(corner) = corner else {
+            return self;
+        };
+        let (row, col) = self.screen.cursor_position();
+        self.corner_status(corner, format!("({row}, {col})"))
+    }
+
+    /// Adds a widget that is rendered above the terminal content and the cursor.
+    ///
+    /// The `area` is given in screen coordinates (column, row) and translated into buffer
+    /// coordinates on render, so tooltips and popups can be anchored to terminal cells.
+    /// The overlay is clipped to the inner area of the `PseudoTerminal`.
+    /// Overlays are rendered in the order they were added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::{layout::Rect, widgets::Paragraph};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen())
+    ///     .overlay(Rect::new(2, 2, 10, 1), Paragraph::new("tooltip"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn overlay<W: Widget + 'a>(mut self, area: Rect, widget: W) -> Self {
+        self.overlays.push((
+            area,
+            Box::new(move |area, buf: &mut Buffer| widget.render(area, buf)),
+        ));
+        self
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn screen(&self) -> &S {
         self.screen
     }
-}
-
-impl<S: Screen> Widget for PseudoTerminal<'_, S> {
-    #[inline]
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        Clear.render(area, buf);
-        let area = self.block.as_ref().map_or(area, |b| {
-            let inner_area = b.inner(area);
-            b.clone().render(area, buf);
-            inner_area
-        });
-        state::handle(&self, area, buf);
+
+    /// Returns the contents of the screen as plain text.
+    ///
+    /// Trailing whitespace is trimmed, see [`PseudoTerminal::to_plain_text_with`] to configure
+    /// the export.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"hello\r\nworld");
+    /// let pseudo_term = PseudoTerminal::new(parser.screen());
+    /// assert_eq!(pseudo_term.to_plain_text(), "hello\nworld");
+    /// ```
+    #[must_use]
+    pub fn to_plain_text(&self) -> String {
+        self.to_plain_text_with(&TextExportOptions::default())
+    }
+
+    /// Returns the contents of the screen as text, according to the given options.
+    ///
+    /// # Arguments
+    ///
+    /// * `options`: The `TextExportOptions` controlling trimming, padding and the cursor marker.
+    #[must_use]
+    pub fn to_plain_text_with(&self, options: &TextExportOptions) -> String {
+        export::plain_text(self.screen, options, self.tab_width)
+    }
+
+    /// Renders the widget and formats every cell as `symbol+fg+bg+modifiers`.
+    ///
+    /// Unlike the `Debug` output of a [`Buffer`], the format is compact and deterministic,
+    /// which makes it suited for color-sensitive snapshot tests.
+    /// Every row of the `area` is a line, cells are separated by a space.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::layout::Rect;
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(1, 2, 0);
+    /// parser.process(b"\x1b[1mx");
+    /// let rendered = PseudoTerminal::new(parser.screen())
+    ///     .render_cursor(false)
+    ///     .debug_render_string(Rect::new(0, 0, 2, 1));
+    /// assert_eq!(rendered, "x+Reset+Reset+BOLD  +Reset+Reset+-");
+    /// ```
+    #[must_use]
+    pub fn debug_render_string(self, area: Rect) -> String {
+        let mut buf = Buffer::empty(area);
+        self.render(area, &mut buf);
+        export::debug_string(&buf)
+    }
+
+    /// Returns the `http://` and `https://` URLs in the text of the screen.
+    ///
+    /// Unlike OSC 8 hyperlinks, these are found by scanning the printed text,
+    /// e.g. to open them on click. The rectangles are in screen coordinates,
+    /// `x` being the column and `y` the row.
+    /// A URL, that continues on a wrapped row, is returned once for every row it covers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::layout::Rect;
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"see https://ratatui.rs.");
+    /// let urls = PseudoTerminal::new(parser.screen()).detect_urls();
+    /// assert_eq!(
+    ///     urls,
+    ///     [(Rect::new(4, 0, 18, 1), "https://ratatui.rs".to_string())]
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn detect_urls(&self) -> Vec<(Rect, String)> {
+        export::detect_urls(self.screen)
+    }
+
+    /// Returns a digest of the text and styles of every cell of the screen.
+    ///
+    /// Equal screens have equal digests, independent of the widget configuration and the
+    /// platform, so tests can compare terminal output without snapshot files.
+    /// The cursor is not part of the digest.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"\x1b[1mhello");
+    /// let digest = PseudoTerminal::new(parser.screen()).content_digest();
+    /// assert_ne!(
+    ///     digest,
+    ///     PseudoTerminal::new(vt100::Parser::new(24, 80, 0).screen()).content_digest()
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn content_digest(&self) -> u64 {
+        export::content_digest(self.screen)
+    }
+
+    /// Returns the content and style of the cells in the given rectangle of the screen.
+    ///
+    /// The rectangle is in screen coordinates, `x` being the column and `y` the row, and the
+    /// cells are returned row by row. Cells outside of the screen are empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::layout::Rect;
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"ab\r\ncd");
+    /// let region = PseudoTerminal::new(parser.screen()).region(Rect::new(0, 0, 2, 2));
+    /// assert_eq!(region[1][0].symbol(), "c");
+    /// ```
+    #[must_use]
+    pub fn region(&self, rect: Rect) -> Vec<Vec<CellInfo>> {
+        rect.rows()
+            .map(|row| {
+                row.columns()
+                    .map(|position| {
+                        let mut cell = CellInfo::default();
+                        if let Some(screen_cell) = self.screen.cell(position.y, position.x) {
+                            screen_cell.apply(&mut cell);
+                        }
+                        cell
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the most common background color of the cells.
+    ///
+    /// Named and indexed colors are resolved with the default xterm palette and returned as
+    /// [`Color::Rgb`], so that equal colors are counted together. [`Color::Reset`] is returned
+    /// if most cells use the default background of the host terminal.
+    /// This allows matching the surrounding chrome, e.g. the block, to the terminal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::Color;
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(1, 3, 0);
+    /// parser.process(b"\x1b[44m   ");
+    /// let background = PseudoTerminal::new(parser.screen()).dominant_background();
+    /// assert_eq!(background, Color::Rgb(0, 0, 238));
+    /// ```
+    #[must_use]
+    pub fn dominant_background(&self) -> Color {
+        let (rows, cols) = self.screen.size();
+        let mut counts: Vec<(Color, usize)> = Vec::new();
+        for (row, col) in (0..rows).flat_map(|row| (0..cols).map(move |col| (row, col))) {
+            let bg = self
+                .screen
+                .cell(row, col)
+                .map_or(Color::Reset, |cell| cell.bg());
+            let bg = palette::rgb(bg).map_or(Color::Reset, |(r, g, b)| Color::Rgb(r, g, b));
+            match counts.iter_mut().find(|(color, _)| *color == bg) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((bg, 1)),
+            }
+        }
+        // The first of the most common colors wins a tie
+        counts
+            .into_iter()
+            .rev()
+            .max_by_key(|(_, count)| *count)
+            .map_or(Color::Reset, |(color, _)| color)
+    }
+
+    /// Returns the style of the cell under the cursor.
+    ///
+    /// If the cell has no contents, the default style is returned.
+    /// This allows overlays, like an input line of a REPL, to match the attributes of the
+    /// terminal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::{Modifier, Style};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"\x1b[1mhello\x1b[D");
+    /// let style = PseudoTerminal::new(parser.screen()).cursor_style();
+    /// assert!(style.add_modifier.contains(Modifier::BOLD));
+    /// ```
+    #[must_use]
+    pub fn cursor_style(&self) -> Style {
+        let (row, col) = self.screen.cursor_position();
+        match self.screen.cell(row, col) {
+            Some(cell) if cell.has_contents() => {
+                let mut scratch = ratatui::buffer::Cell::default();
+                cell.apply(&mut scratch);
+                scratch.style()
+            }
+            _ => Style::default(),
+        }
+    }
+
+    /// Returns whether the cursor is at the start of an empty line.
+    ///
+    /// This is a heuristic for a shell waiting at a fresh line, e.g. after a command finished
+    /// and before the prompt is printed. Apps can combine it with following the output, to
+    /// only scroll to the bottom when the program is idle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"done\r\n");
+    /// assert!(PseudoTerminal::new(parser.screen()).cursor_at_line_start());
+    /// ```
+    #[must_use]
+    pub fn cursor_at_line_start(&self) -> bool {
+        let (row, col) = self.screen.cursor_position();
+        let cols = self.screen.size().1;
+        col == 0
+            && !(0..cols).any(|col| {
+                self.screen
+                    .cell(row, col)
+                    .is_some_and(|cell| !cell.contents().trim().is_empty())
+            })
+    }
+
+    /// Translates a buffer position into the (row, column) the program addresses it with.
+    ///
+    /// The `state` must be the one the widget was last rendered with.
+    /// In [origin mode](Screen::origin_mode), the row is relative to the top of the scroll
+    /// region, positions outside of the scroll region can't be addressed and return `None`.
+    /// `vt100` screens don't report origin mode, so their rows are always absolute.
+    /// Positions outside of the drawn area return `None` as well.
+    ///
+    /// Use [`PseudoTerminal::cell_at`] to get the cell at the position.
+    #[must_use]
+    pub fn screen_coords(
+        &self,
+        state: &PseudoTerminalState,
+        buf_row: u16,
+        buf_col: u16,
+    ) -> Option<(u16, u16)> {
+        let (row, col) = state.visible_coords(buf_row, buf_col)?;
+        if !self.screen.origin_mode() {
+            return Some((row, col));
+        }
+        let (top, bottom) = self.screen.scroll_region();
+        (top..=bottom).contains(&row).then(|| (row - top, col))
+    }
+
+    /// Returns the cell of the screen drawn at the buffer position.
+    ///
+    /// The `state` must be the one the widget was last rendered with.
+    /// Unlike [`PseudoTerminal::screen_coords`], this doesn't depend on origin mode.
+    #[must_use]
+    pub fn cell_at(
+        &self,
+        state: &PseudoTerminalState,
+        buf_row: u16,
+        buf_col: u16,
+    ) -> Option<&S::C> {
+        let (row, col) = state.visible_coords(buf_row, buf_col)?;
+        self.screen.cell(row, col)
+    }
+
+    /// Returns what is under a click at `position`, if the widget is rendered to `area`.
+    ///
+    /// URLs, as found by [`PseudoTerminal::detect_urls`], take precedence over words, which
+    /// are runs of cells that aren't whitespace. This combines the hit-testing and the word
+    /// lookup, so that apps can route the click to open a link or select a word, e.g. with
+    /// [`PseudoTerminal::selection`].
+    /// Clicks outside of the drawn screen, e.g. on the block, return `None`. The position of
+    /// a crossterm `MouseEvent` is `Position::new(event.column, event.row)`.
+    /// Downscaling through [`PseudoTerminal::scale_to_fit`] isn't taken into account.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::layout::{Position, Rect};
+    /// use tui_term::widget::{ClickAction, PseudoTerminal};
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"see https://ratatui.rs");
+    /// let pseudo_term = PseudoTerminal::new(parser.screen());
+    /// let area = Rect::new(0, 0, 80, 24);
+    /// assert_eq!(
+    ///     pseudo_term.handle_click(area, Position::new(8, 0)),
+    ///     Some(ClickAction::Link {
+    ///         url: "https://ratatui.rs".to_string(),
+    ///         bounds: Rect::new(4, 0, 18, 1),
+    ///     })
+    /// );
+    /// ```
+    #[must_use]
+    pub fn handle_click(&self, area: Rect, position: Position) -> Option<ClickAction> {
+        let inner_area = self.inner_area(area);
+        if !inner_area.contains(position) {
+            return None;
+        }
+        let row = position.y - inner_area.y;
+        let col = (position.x - inner_area.x).saturating_add(self.h_offset);
+        let (rows, cols) = self.screen.size();
+        if row >= rows || col >= cols {
+            return None;
+        }
+        let link = self
+            .detect_urls()
+            .into_iter()
+            .find(|(bounds, _)| bounds.contains(Position::new(col, row)));
+        if let Some((bounds, url)) = link {
+            return Some(ClickAction::Link { url, bounds });
+        }
+        Some(match export::word_at(self.screen, row, col) {
+            Some((cols, text)) => ClickAction::Word {
+                text,
+                bounds: Rect::new(cols.start, row, cols.end - cols.start, 1),
+            },
+            None => ClickAction::Empty { row, col },
+        })
+    }
+
+    /// Returns the text of the screen, rewrapped to `width` columns at word boundaries.
+    ///
+    /// Rows that wrap into the next row are joined into a logical line first, which is then
+    /// wrapped again. Words longer than `width` are broken, whitespace between words is
+    /// collapsed and trailing empty lines are skipped.
+    /// This is meant for exports into narrower documents, e.g. as Markdown.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"hello wide world");
+    /// let text = PseudoTerminal::new(parser.screen()).reflow_text(11);
+    /// assert_eq!(text, "hello wide\nworld");
+    /// ```
+    #[must_use]
+    pub fn reflow_text(&self, width: u16) -> String {
+        export::reflow(self.screen, width)
+    }
+
+    /// Returns a tiny silhouette of the screen in Braille patterns, that fits into the area.
+    ///
+    /// Every Braille character has 2x4 dots, a dot is raised if the cells of the screen it
+    /// covers are not blank. Larger screens are downsampled, so the preview is at most as
+    /// large as the area. The preview is lossy and meant for compact overviews, e.g. of many
+    /// sessions. The rows are stretched by the [cell aspect](PseudoTerminal::cell_aspect).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::layout::Rect;
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"hello");
+    /// let preview = PseudoTerminal::new(parser.screen()).braille_preview(Rect::new(0, 0, 20, 3));
+    /// assert_eq!(preview.lines.len(), 3);
+    /// ```
+    #[must_use]
+    pub fn braille_preview(&self, area: Rect) -> Text<'static> {
+        let lines: Vec<Line<'static>> =
+            export::braille(self.screen, area.width, area.height, self.cell_aspect)
+                .into_iter()
+                .map(Line::from)
+                .collect();
+        Text::from(lines)
+    }
+
+    /// Returns the styled spans of the given screen row.
+    ///
+    /// Adjacent cells with the same style are merged into a single span, the continuation
+    /// cells of wide characters are skipped. A row outside of the screen has no spans.
+    /// This makes assertions on the styling possible without snapshots.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::Modifier;
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"\x1b[1mbold\x1b[0m plain");
+    /// let spans = PseudoTerminal::new(parser.screen()).row_spans(0);
+    /// assert_eq!(spans[0].content, "bold");
+    /// assert!(spans[0].style.add_modifier.contains(Modifier::BOLD));
+    /// ```
+    #[must_use]
+    pub fn row_spans(&self, row: u16) -> Vec<Span<'static>> {
+        export::row_spans(self.screen, row)
+    }
+
+    /// Splits the screen into a [`Table`] at the given column boundaries.
+    ///
+    /// Every boundary is the first column of a new table column, e.g. `&[10, 20]` splits
+    /// every row into the columns `0..10`, `10..20` and `20..`.
+    /// Cells are trimmed and trailing blank rows are skipped.
+    ///
+    /// This is experimental and heuristic: it is meant for aligned output like `ls -l`,
+    /// values that overflow their column end up in the neighbouring cell.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"name    size\r\nfoo.rs  42");
+    /// let table = PseudoTerminal::new(parser.screen()).to_table(&[8]);
+    /// ```
+    #[must_use]
+    pub fn to_table(&self, column_seps: &[u16]) -> Table<'static> {
+        let (_, cols) = self.screen.size();
+        let bounds = export::column_bounds(cols, column_seps);
+        let rows = export::table_cells(self.screen, &bounds)
+            .into_iter()
+            .map(Row::new);
+        let widths = bounds
+            .windows(2)
+            .map(|bound| Constraint::Length(bound[1] - bound[0]));
+        Table::new(rows, widths)
+    }
+
+    /// Returns the bounding box of all cells with contents, in screen coordinates.
+    ///
+    /// This is useful for cropping whitespace-heavy output, e.g. for screenshots.
+    /// An empty screen has empty bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::layout::Rect;
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"hello");
+    /// let pseudo_term = PseudoTerminal::new(parser.screen());
+    /// assert_eq!(pseudo_term.content_bounds(), Rect::new(0, 0, 5, 1));
+    /// ```
+    #[must_use]
+    pub fn content_bounds(&self) -> Rect {
+        let (rows, cols) = self.screen.size();
+        let mut bounds: Option<(u16, u16, u16, u16)> = None;
+        for row in 0..rows {
+            for col in 0..cols {
+                if !self
+                    .screen
+                    .cell(row, col)
+                    .is_some_and(|cell| cell.has_contents())
+                {
+                    continue;
+                }
+                bounds = Some(match bounds {
+                    Some((top, left, bottom, right)) => {
+                        (top.min(row), left.min(col), bottom.max(row), right.max(col))
+                    }
+                    None => (row, col, row, col),
+                });
+            }
+        }
+        bounds.map_or_else(Rect::default, |(top, left, bottom, right)| {
+            Rect::new(left, top, right - left + 1, bottom - top + 1)
+        })
+    }
+
+    /// Draws only the cursor to the buffer.
+    ///
+    /// The `area` is the same area the widget is rendered to, the cursor is placed
+    /// inside the block, if one is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let area = Rect::new(0, 0, 80, 24);
+    /// let mut buf = Buffer::empty(area);
+    /// let mut overlay = Buffer::empty(area);
+    /// let pseudo_term = PseudoTerminal::new(parser.screen()).render_cursor(false);
+    /// pseudo_term.draw_cursor(area, &mut overlay);
+    /// pseudo_term.render(area, &mut buf);
+    /// ```
+    #[inline]
+    pub fn draw_cursor(&self, area: Rect, buf: &mut Buffer) {
+        state::handle_cursor(self, self.inner_area(area), buf);
+    }
+
+    /// Moves the cursor in a buffer the widget was previously rendered to.
+    ///
+    /// The cell at `prev_pos` (row, column) is redrawn from the screen, which removes the
+    /// cursor, before the cursor is drawn at its current position.
+    /// No other cell is touched, so this is much cheaper than rendering the whole widget,
+    /// if only the cursor moved.
+    /// The cursor line and column highlights and the debug grid are not updated,
+    /// and the buffer must not be downscaled through [`PseudoTerminal::scale_to_fit`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_term::widget::{PseudoTerminal, Screen};
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let area = Rect::new(0, 0, 80, 24);
+    /// let mut buf = Buffer::empty(area);
+    /// PseudoTerminal::new(parser.screen()).render(area, &mut buf);
+    /// let prev_pos = parser.screen().cursor_position();
+    /// parser.process(b"\x1b[2;5H");
+    /// PseudoTerminal::new(parser.screen()).update_cursor_only(prev_pos, area, &mut buf);
+    /// ```
+    #[inline]
+    pub fn update_cursor_only(&self, prev_pos: (u16, u16), area: Rect, buf: &mut Buffer) {
+        let inner_area = self.inner_area(area);
+        state::handle_cell(self, prev_pos, inner_area, buf);
+        state::handle_cursor(self, inner_area, buf);
+    }
+
+    /// Returns where the cursor is drawn, if the widget is rendered to `area`.
+    ///
+    /// The position is absolute and accounts for the block, panning, downscaling, flipping and
+    /// rotation. It is `None` if the cursor is hidden, see [`Cursor::visibility`], or outside of
+    /// the area.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::layout::{Position, Rect};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"$ ");
+    /// let pseudo_term = PseudoTerminal::new(parser.screen());
+    /// let position = pseudo_term.frame_cursor_position(Rect::new(5, 5, 80, 24));
+    /// assert_eq!(position, Some(Position::new(7, 5)));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn frame_cursor_position(&self, area: Rect) -> Option<Position> {
+        state::frame_cursor_position(self, self.inner_area(area)).map(|(x, y)| Position::new(x, y))
+    }
+
+    /// Positions the hardware cursor of the terminal on the cursor of the screen.
+    ///
+    /// Unlike the drawn cursor, the native cursor blinks and takes the shape the user has
+    /// configured. Disable the drawn cursor with [`PseudoTerminal::render_cursor`] to show
+    /// only the native one. Nothing is set, if the cursor is hidden or outside of the area,
+    /// which leaves the hardware cursor hidden for the frame.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::{backend::TestBackend, Terminal};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    /// terminal
+    ///     .draw(|frame| {
+    ///         let area = frame.area();
+    ///         let pseudo_term = PseudoTerminal::new(parser.screen()).render_cursor(false);
+    ///         pseudo_term.set_frame_cursor(frame, area);
+    ///         frame.render_widget(pseudo_term, area);
+    ///     })
+    ///     .unwrap();
+    /// ```
+    #[inline]
+    pub fn set_frame_cursor(&self, frame: &mut Frame, area: Rect) {
+        if let Some(position) = self.frame_cursor_position(area) {
+            frame.set_cursor_position(position);
+        }
+    }
+
+    /// Renders like the [`StatefulWidget`](ratatui::widgets::StatefulWidget) and returns the
+    /// (x, y) buffer cells, that changed from the previous frame in the buffer.
+    ///
+    /// The widget rewrites its whole area, the report is the damage a diffing backend would
+    /// flush. This makes incremental updates testable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::{buffer::Buffer, layout::Rect};
+    /// use tui_term::widget::{PseudoTerminal, PseudoTerminalState};
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let mut state = PseudoTerminalState::new();
+    /// let area = Rect::new(0, 0, 80, 24);
+    /// let mut buf = Buffer::empty(area);
+    /// let damage = PseudoTerminal::new(parser.screen()).render_and_report(&mut state, area, &mut buf);
+    /// ```
+    #[must_use]
+    pub fn render_and_report(
+        self,
+        prev_state: &mut PseudoTerminalState,
+        area: Rect,
+        buf: &mut Buffer,
+    ) -> Vec<(u16, u16)> {
+        let prev = buf.clone();
+        ratatui::widgets::StatefulWidget::render(self, area, buf, prev_state);
+        let area = buf.area;
+        area.rows()
+            .flat_map(Rect::columns)
+            .filter(|cell| buf[(cell.x, cell.y)] != prev[(cell.x, cell.y)])
+            .map(|cell| (cell.x, cell.y))
+            .collect()
+    }
+
+    /// Redraws the given (row, column) cells of the screen in a buffer the widget was
+    /// previously rendered to.
+    ///
+    /// No other cell is touched, which makes this a cheap primitive for applications that
+    /// track dirty regions themselves. Like [`PseudoTerminal::update_cursor_only`], it
+    /// doesn't update the cursor, highlights or the debug grid, and the buffer must not be
+    /// downscaled through [`PseudoTerminal::scale_to_fit`].
+    /// Cells outside of the screen or the area are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_term::widget::PseudoTerminal;
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// let area = Rect::new(0, 0, 80, 24);
+    /// let mut buf = Buffer::empty(area);
+    /// PseudoTerminal::new(parser.screen()).render(area, &mut buf);
+    /// parser.process(b"ab");
+    /// PseudoTerminal::new(parser.screen()).render_dirty(&[(0, 0), (0, 1)], area, &mut buf);
+    /// ```
+    #[inline]
+    pub fn render_dirty(&self, dirty: &[(u16, u16)], area: Rect, buf: &mut Buffer) {
+        let inner_area = self.inner_area(area);
+        for &position in dirty {
+            state::handle_cell(self, position, inner_area, buf);
+        }
+    }
+
+    /// The area the screen is drawn to, inside the block if one is set.
+    #[inline]
+    pub(crate) fn inner_area(&self, area: Rect) -> Rect {
+        let inner_area = self.content_area(self.trimmed_area(area));
+        if self.letterbox.is_none() {
+            return inner_area;
+        }
+        // Center the screen, if it is smaller than the area
+        let (rows, cols) = self.screen.size();
+        let width = inner_area.width.min(cols);
+        let height = inner_area.height.min(rows);
+        Rect::new(
+            inner_area.x + (inner_area.width - width) / 2,
+            inner_area.y + (inner_area.height - height) / 2,
+            width,
+            height,
+        )
+    }
+
+    /// The area the screen is drawn to, before trailing blank rows are trimmed.
+    fn content_area(&self, area: Rect) -> Rect {
+        let area = self.outer_area(area);
+        let mut inner_area = self.block.as_ref().map_or(area, |b| b.inner(area));
+        if let Some(max_cols) = self.max_cols {
+            inner_area.width = inner_area.width.min(max_cols);
+        }
+        inner_area
+    }
+
+    /// The RGB color cells are blended towards, if they are not fully opaque.
+    #[inline]
+    pub(crate) fn blend_background(&self) -> (u8, u8, u8) {
+        self.style
+            .and_then(|style| style.bg)
+            .and_then(palette::rgb)
+            .unwrap_or((0, 0, 0))
+    }
+
+    /// Whether the area is reset before drawing.
+    #[inline]
+    pub(crate) const fn clears_area(&self) -> bool {
+        self.reset_area_first
+            && self.transparent_color.is_none()
+            && self.transparent_colors.is_empty()
+    }
+
+    /// The area without the trailing blank rows of the screen, if they are trimmed.
+    fn trimmed_area(&self, area: Rect) -> Rect {
+        if !self.trim_trailing_blank_rows {
+            return area;
+        }
+        let screen = self.screen();
+        let (rows, cols) = screen.size();
+        let cursor_row = screen.cursor_position().0;
+        let used_rows = (0..rows)
+            .rev()
+            .find(|&row| {
+                row == cursor_row
+                    || (0..cols).any(|col| screen.cell(row, col).is_some_and(Cell::has_contents))
+            })
+            .map_or(0, |row| row + 1);
+        let chrome = area.height - self.content_area(area).height;
+        Rect {
+            height: area.height.min(used_rows.saturating_add(chrome)),
+            ..area
+        }
+    }
+
+    /// The area of the block, without the shadow if one is set.
+    #[inline]
+    fn outer_area(&self, area: Rect) -> Rect {
+        if self.shadow.is_some() {
+            Rect::new(
+                area.x,
+                area.y,
+                area.width.saturating_sub(1),
+                area.height.saturating_sub(1),
+            )
+        } else {
+            area
+        }
+    }
+}
+
+impl<'a> PseudoTerminal<'a, dyn DynScreen + 'a> {
+    /// Creates a new instance of `PseudoTerminal` from a type-erased screen.
+    ///
+    /// This allows terminals of different backends to be held uniformly.
+    ///
+    /// # Arguments
+    ///
+    /// * `screen`: The reference to the `DynScreen`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::{DynScreen, PseudoTerminal};
+    ///
+    /// let parser = vt100::Parser::new(24, 80, 0);
+    /// let screens: Vec<Box<dyn DynScreen>> = vec![Box::new(parser.screen().clone())];
+    /// let pseudo_term = PseudoTerminal::from_dyn(screens[0].as_ref());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_dyn(screen: &'a (dyn DynScreen + 'a)) -> Self {
+        Self::new(screen)
+    }
+}
+
+impl<S: Screen + ?Sized> Widget for PseudoTerminal<'_, S> {
+    #[inline]
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
+        if let Some(mask) = self.clip_mask.take() {
+            let masked = masked_cells(area, buf, &mask);
+            Widget::render(self, area, buf);
+            restore_cells(buf, masked);
+            return;
+        }
+        if self.static_render {
+            self.render_cursor = true;
+            self.cursor.show = true;
+            self.cursor.force_show = true;
+            self.cursor.symbol = Cursor::default().symbol;
+            self.cursor.bar_width = None;
+            self.cursor.placement = CursorPlacement::Symbol;
+        }
+        let area = self.trimmed_area(area);
+        if let Some(style) = self.shadow {
+            let area = area.intersection(buf.area);
+            if area.width > 1 && area.height > 1 {
+                let right = Rect::new(area.right() - 1, area.y + 1, 1, area.height - 1);
+                let bottom = Rect::new(area.x + 1, area.bottom() - 1, area.width - 1, 1);
+                buf.set_style(right, style);
+                buf.set_style(bottom, style);
+            }
+        }
+        let inner_area = self.inner_area(area);
+        let area = self.outer_area(area);
+        if self.clears_area() {
+            Clear.render(area, buf);
+            if let Some(style) = self.clear_style {
+                buf.set_style(area, style);
+            }
+        }
+        if let Some(mut block) = self.block.clone() {
+            let title = self.screen.title();
+            if self.auto_title && !title.is_empty() {
+                let style = self
+                    .title_style_fn
+                    .as_ref()
+                    .map_or_else(Style::default, |style| style(title));
+                block = block.title(Line::styled(title, style));
+            }
+            block.render(area, buf);
+        }
+        if let Some(style) = self.letterbox {
+            buf.set_style(self.content_area(area).intersection(buf.area), style);
+        }
+        state::handle(&self, inner_area, buf);
+        for (corner, status) in self.corner_statuses {
+            render_corner_status(corner, status, inner_area, buf);
+        }
+        let lock = self
+            .mask_input
+            .or_else(|| self.screen.echo_disabled().then_some(Corner::TopRight));
+        if let Some(corner) = lock {
+            render_corner_status(corner, Line::raw(LOCK_GLYPH), inner_area, buf);
+        }
+        for (overlay_area, render) in self.overlays {
+            // Follow the horizontal panning, the part left of the view is cut off
+            let hidden = self.h_offset.saturating_sub(overlay_area.x);
+            let overlay_area = Rect::new(
+                inner_area
+                    .x
+                    .saturating_add(overlay_area.x.saturating_sub(self.h_offset)),
+                inner_area.y.saturating_add(overlay_area.y),
+                overlay_area.width.saturating_sub(hidden),
+                overlay_area.height,
+            )
+            .intersection(inner_area);
+            if !overlay_area.is_empty() {
+                render(overlay_area, buf);
+            }
+        }
+        if self.static_render {
+            let blink = Modifier::SLOW_BLINK | Modifier::RAPID_BLINK;
+            buf.set_style(
+                area.intersection(buf.area),
+                Style::new().remove_modifier(blink),
+            );
+        }
+    }
+}
+
+/// The indicator of [`PseudoTerminal::mask_input`].
+const LOCK_GLYPH: &str = "🔒";
+
+/// Renders a status in a corner of the area, truncated to its width.
+fn render_corner_status(corner: Corner, status: Line, area: Rect, buf: &mut Buffer) {
+    let width = (status.width() as u16).min(area.width);
+    let x = match corner {
+        Corner::TopLeft | Corner::BottomLeft => area.left(),
+        Corner::TopRight | Corner::BottomRight => area.right() - width,
+    };
+    let y = match corner {
+        Corner::TopLeft | Corner::TopRight => area.top(),
+        Corner::BottomLeft | Corner::BottomRight => area.bottom().saturating_sub(1),
+    };
+    let status_area = Rect::new(x, y, width, 1).intersection(area);
+    if !status_area.is_empty() {
+        status.render(status_area, buf);
+    }
+}
+
+/// The scroll offset as a percentage of the scrollback length, e.g. `"47%"`.
+fn scroll_percentage(offset: usize, scrollback_len: usize) -> String {
+    let percentage = (offset.saturating_mul(100) / scrollback_len.max(1)).min(100);
+    format!("{percentage}%")
+}
+
+/// Cells of a buffer with their (x, y) position, to be restored later.
+type SavedCells = Vec<(u16, u16, ratatui::buffer::Cell)>;
+
+/// The cells of the area, that the clip mask keeps from being drawn to.
+fn masked_cells(area: Rect, buf: &Buffer, mask: &ClipMask<'_>) -> SavedCells {
+    let area = area.intersection(buf.area);
+    let mut cells = Vec::new();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            if !mask(x, y) {
+                cells.push((x, y, buf[(x, y)].clone()));
+            }
+        }
+    }
+    cells
+}
+
+/// The cells of the area, row by row.
+fn area_cells(area: Rect, buf: &Buffer) -> Vec<ratatui::buffer::Cell> {
+    area.intersection(buf.area)
+        .positions()
+        .map(|position| buf[position].clone())
+        .collect()
+}
+
+/// Write the cells of the area, row by row, as returned by [`area_cells`].
+fn set_area_cells(area: Rect, buf: &mut Buffer, cells: &[ratatui::buffer::Cell]) {
+    for (position, cell) in area.intersection(buf.area).positions().zip(cells) {
+        buf[position] = cell.clone();
+    }
+}
+
+/// Put back the cells, that were kept from being drawn to.
+fn restore_cells(buf: &mut Buffer, cells: SavedCells) {
+    for (x, y, cell) in cells {
+        buf[(x, y)] = cell;
+    }
+}
+
+/// The cells written to the buffer under a [render budget](PseudoTerminalState::render_budget).
+#[derive(Debug, Default, Clone)]
+struct BudgetedFrame {
+    area: Rect,
+    /// The cells written so far, row by row.
+    shown: Vec<ratatui::buffer::Cell>,
+    /// The cells of the last rendered frame, to find the rows that changed.
+    rendered: Vec<ratatui::buffer::Cell>,
+    /// The frame each row last changed in.
+    changed: Vec<u64>,
+    frame: u64,
+}
+
+impl BudgetedFrame {
+    /// Starts from the cells of the area, before anything was rendered to it.
+    fn new(area: Rect, buf: &Buffer) -> Self {
+        let cells = area_cells(area, buf);
+        Self {
+            area,
+            shown: cells.clone(),
+            rendered: cells,
+            changed: vec![0; area.height.into()],
+            frame: 0,
+        }
+    }
+
+    /// Writes at most `budget` changed cells of the rendered frame in the buffer, the rows
+    /// that changed most recently first, and puts back the cells written before elsewhere.
+    fn write(&mut self, budget: usize, buf: &mut Buffer) {
+        self.frame += 1;
+        let width = usize::from(self.area.width);
+        let rendered = area_cells(self.area, buf);
+        for (row, changed) in self.changed.iter_mut().enumerate() {
+            let cells = row * width..(row + 1) * width;
+            if rendered[cells.clone()] != self.rendered[cells] {
+                *changed = self.frame;
+            }
+        }
+        let mut rows: Vec<usize> = (0..self.changed.len()).collect();
+        rows.sort_by_key(|&row| std::cmp::Reverse(self.changed[row]));
+        let mut budget = budget;
+        for i in rows
+            .into_iter()
+            .flat_map(|row| row * width..(row + 1) * width)
+        {
+            if budget == 0 {
+                break;
+            }
+            if self.shown[i] != rendered[i] {
+                self.shown[i] = rendered[i].clone();
+                budget -= 1;
+            }
+        }
+        set_area_cells(self.area, buf, &self.shown);
+        self.rendered = rendered;
+    }
+}
+
+/// The state of a [`PseudoTerminal`] that is kept between renders.
+///
+/// It records where the screen was last drawn, so that positions in the buffer,
+/// e.g. of mouse clicks, can be translated back into screen coordinates.
+///
+/// # Example
+///
+/// ```
+/// use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+/// use tui_term::widget::{PseudoTerminal, PseudoTerminalState};
+///
+/// let mut parser = vt100::Parser::new(24, 80, 100);
+/// let mut state = PseudoTerminalState::new();
+/// let area = Rect::new(0, 0, 80, 24);
+/// let mut buf = Buffer::empty(area);
+/// PseudoTerminal::new(parser.screen()).render(area, &mut buf, &mut state);
+/// ```
+#[derive(Debug, Default, Clone)]
+#[non_exhaustive]
+pub struct PseudoTerminalState {
+    pub(crate) area: Rect,
+    pub(crate) scroll_offset: usize,
+    pub(crate) h_offset: u16,
+    pub(crate) new_output: bool,
+    scrollback_len: usize,
+    pub(crate) live_line: Vec<ratatui::buffer::Cell>,
+    frozen_frame: Option<(Rect, Vec<ratatui::buffer::Cell>)>,
+    render_budget: Option<usize>,
+    budgeted_frame: Option<BudgetedFrame>,
+}
+
+impl PseudoTerminalState {
+    /// Creates a new, empty `PseudoTerminalState`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the number of cells written per render, e.g. for very large terminals on slow
+    /// backends (default = no limit).
+    ///
+    /// Every render still draws the whole frame, but only the first `cells` cells, that
+    /// differ from the ones written before, make it into the buffer. The rows that changed
+    /// most recently are written first, the rest is deferred to the next renders, until the
+    /// buffer shows the whole frame. This trades latency for frame rate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+    /// use tui_term::widget::{PseudoTerminal, PseudoTerminalState};
+    ///
+    /// let mut parser = vt100::Parser::new(24, 80, 0);
+    /// parser.process(b"hello");
+    /// let mut state = PseudoTerminalState::new().render_budget(2);
+    /// let area = Rect::new(0, 0, 80, 24);
+    /// let mut buf = Buffer::empty(area);
+    /// PseudoTerminal::new(parser.screen()).render(area, &mut buf, &mut state);
+    /// assert_eq!(buf[(1, 0)].symbol(), "e");
+    /// assert_eq!(buf[(2, 0)].symbol(), " ");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn render_budget(mut self, cells: usize) -> Self {
+        self.render_budget = Some(cells);
+        self
+    }
+
+    /// The area the screen was last drawn to, inside the block if one is set.
+    #[inline]
+    #[must_use]
+    pub const fn area(&self) -> Rect {
+        self.area
+    }
+
+    /// The number of rows the view is scrolled back into the scrollback.
+    #[inline]
+    #[must_use]
+    pub const fn scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+
+    /// Sets the number of rows the view is scrolled back into the scrollback.
+    ///
+    /// This should match the offset of the screen,
+    /// e.g. the value passed to `vt100::Parser::set_scrollback`.
+    #[inline]
+    pub fn set_scroll_offset(&mut self, scroll_offset: usize) {
+        self.scroll_offset = scroll_offset;
+        if self.at_bottom() {
+            self.new_output = false;
+        }
+    }
+
+    /// Sets the number of rows in the scrollback, for the
+    /// [scroll percentage label](PseudoTerminal::scroll_percentage_label).
+    ///
+    /// `vt100` doesn't expose the length, but clamps the offset to it, so it can be read back
+    /// after `parser.set_scrollback(usize::MAX)`.
+    #[inline]
+    pub fn set_scrollback_len(&mut self, scrollback_len: usize) {
+        self.scrollback_len = scrollback_len;
+    }
+
+    /// Whether the view shows the live screen, i.e. it is not scrolled up.
+    #[inline]
+    #[must_use]
+    pub const fn at_bottom(&self) -> bool {
+        self.scroll_offset == 0
+    }
+
+    /// Records that new output arrived.
+    ///
+    /// While the view is scrolled up, this shows the
+    /// [new output indicator](PseudoTerminal::new_output_indicator) until the view is
+    /// scrolled back to the bottom.
+    #[inline]
+    pub fn notify_new_output(&mut self) {
+        self.new_output = !self.at_bottom();
+    }
+
+    /// Forgets everything recorded by previous renders, e.g. after a resize or a resume from
+    /// suspend.
+    ///
+    /// This drops the drawn area, the [live line](PseudoTerminal::peek_live_line) and the
+    /// [frozen](PseudoTerminal::frozen) frame captured for the old size, as well as the cells
+    /// written under the [render budget](PseudoTerminalState::render_budget). The scroll
+    /// offsets are kept.
+    /// Without a render budget, the widget never skips unchanged cells, every render
+    /// rewrites the whole area.
+    /// To make the backend repaint the host terminal as well, call `Terminal::clear`.
+    #[inline]
+    pub fn invalidate(&mut self) {
+        self.area = Rect::default();
+        self.live_line.clear();
+        self.frozen_frame = None;
+        self.budgeted_frame = None;
+    }
+
+    /// The number of columns the view is panned to the right.
+    #[inline]
+    #[must_use]
+    pub const fn h_offset(&self) -> u16 {
+        self.h_offset
+    }
+
+    /// Pans the view `columns` to the left, stopping at the first column of the screen.
+    #[inline]
+    pub fn scroll_left(&mut self, columns: u16) {
+        self.h_offset = self.h_offset.saturating_sub(columns);
+    }
+
+    /// Pans the view `columns` to the right.
+    ///
+    /// This reveals the end of lines, that are wider than the area the screen is drawn to.
+    /// The offset is clamped on the next render, so that the view never pans past the last
+    /// column of the screen.
+    /// Panning is ignored while the screen is downscaled through
+    /// [`PseudoTerminal::scale_to_fit`].
+    #[inline]
+    pub fn scroll_right(&mut self, columns: u16) {
+        self.h_offset = self.h_offset.saturating_add(columns);
+    }
+
+    /// The (row, column) of the screen drawn at the buffer position, if it is inside the area.
+    pub(crate) fn visible_coords(&self, buf_row: u16, buf_col: u16) -> Option<(u16, u16)> {
+        let position = ratatui::layout::Position::new(buf_col, buf_row);
+        self.area.contains(position).then(|| {
+            (
+                buf_row - self.area.y,
+                (buf_col - self.area.x).saturating_add(self.h_offset),
+            )
+        })
+    }
+
+    /// Translates a buffer position into an absolute (row, column) of the terminal.
+    ///
+    /// Row `0` is the first row of the live screen, negative rows are in the scrollback,
+    /// `-1` being the most recent scrollback row.
+    /// The column includes the horizontal offset of the view.
+    /// This keeps selections stable while the view is scrolled.
+    /// Positions left of or above the drawn area are clamped to its edge.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_term::widget::PseudoTerminalState;
+    ///
+    /// let mut state = PseudoTerminalState::new();
+    /// state.set_scroll_offset(3);
+    /// assert_eq!(state.absolute_coords(1, 4), (-2, 4));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn absolute_coords(&self, buf_row: u16, buf_col: u16) -> (isize, u16) {
+        let row = buf_row.saturating_sub(self.area.y) as isize;
+        let col = buf_col
+            .saturating_sub(self.area.x)
+            .saturating_add(self.h_offset);
+        (row - self.scroll_offset as isize, col)
+    }
+}
+
+impl<S: Screen + ?Sized> ratatui::widgets::StatefulWidget for PseudoTerminal<'_, S> {
+    type State = PseudoTerminalState;
+
+    #[inline]
+    fn render(mut self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        if let Some(mask) = self.clip_mask.take() {
+            let masked = masked_cells(area, buf, &mask);
+            ratatui::widgets::StatefulWidget::render(self, area, buf, state);
+            restore_cells(buf, masked);
+            return;
+        }
+        let frozen = self.frozen;
+        if !frozen {
+            state.frozen_frame = None;
+        }
+        let frame_area = area.intersection(buf.area);
+        if let Some((_, cells)) = state
+            .frozen_frame
+            .as_ref()
+            .filter(|(area, _)| *area == frame_area)
+        {
+            set_area_cells(frame_area, buf, cells);
+            return;
+        }
+        if state.render_budget.is_some()
+            && !state
+                .budgeted_frame
+                .as_ref()
+                .is_some_and(|budgeted| budgeted.area == frame_area)
+        {
+            state.budgeted_frame = Some(BudgetedFrame::new(frame_area, buf));
+        }
+        state.area = self.inner_area(area);
+        // Don't pan past the last column of the screen
+        let (_, cols) = self.screen.size();
+        state.h_offset = state.h_offset.min(cols.saturating_sub(state.area.width));
+        self.h_offset = state.h_offset;
+        let indicator = self.new_output_indicator.filter(|_| state.new_output);
+        let percentage_label = self
+            .scroll_percentage_label
+            .filter(|_| !state.at_bottom())
+            .map(|corner| {
+                let label = scroll_percentage(state.scroll_offset, state.scrollback_len);
+                (corner, Line::raw(label))
+            });
+        let peek = self.peek_live_line;
+        let fade = self.scroll_fade.filter(|_| !state.at_bottom());
+        let background = self.blend_background();
+        if peek.is_some() && state.at_bottom() {
+            state.live_line = state::live_line(&self);
+        }
+        Widget::render(self, area, buf);
+
+        if let Some(rows) = fade {
+            state::fade_top(state.area.intersection(buf.area), buf, rows, background);
+        }
+
+        if let Some(style) = peek.filter(|_| !state.at_bottom()) {
+            let inner_area = state.area.intersection(buf.area);
+            if !inner_area.is_empty() {
+                let y = inner_area.bottom() - 1;
+                let cells = state.live_line.iter().skip(state.h_offset.into());
+                for (x, cell) in (inner_area.left()..inner_area.right()).zip(cells) {
+                    let buf_cell = &mut buf[(x, y)];
+                    *buf_cell = cell.clone();
+                    buf_cell.set_style(style);
+                }
+            }
+        }
+
+        if let Some((corner, label)) = percentage_label {
+            render_corner_status(corner, label, state.area.intersection(buf.area), buf);
+        }
+
+        if let Some(indicator) = indicator {
+            let inner_area = state.area.intersection(buf.area);
+            if !inner_area.is_empty() {
+                let width = Span::raw(indicator).width() as u16;
+                let x = inner_area.x + inner_area.width.saturating_sub(width) / 2;
+                let style = Style::default().add_modifier(Modifier::REVERSED);
+                buf.set_stringn(
+                    x,
+                    inner_area.bottom() - 1,
+                    indicator,
+                    inner_area.right().saturating_sub(x).into(),
+                    style,
+                );
+            }
+        }
+
+        if let (Some(budget), Some(budgeted)) = (state.render_budget, &mut state.budgeted_frame) {
+            budgeted.write(budget, buf);
+        }
+
+        if frozen {
+            state.frozen_frame = Some((frame_area, area_cells(frame_area, buf)));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "vt100"))]
+mod tests {
+    use ratatui::{backend::TestBackend, widgets::Borders, Terminal};
+
+    use super::*;
+    use crate::{export::LinedrawMode, snapshot::ScreenSnapshot};
+
+    /// A minimal [`Screen`] implementation, for features the `vt100` backend doesn't expose.
+    struct MockScreen {
+        rows: Vec<Vec<MockCell>>,
+        cursor: (u16, u16),
+        scroll_region: Option<(u16, u16)>,
+        saved_cursor: Option<(u16, u16)>,
+        origin_mode: bool,
+        insert_mode: bool,
+        echo_disabled: bool,
+    }
+
+    #[derive(Default, Clone)]
+    struct MockCell {
+        contents: String,
+        protected: bool,
+        charset: Charset,
+    }
+
+    impl MockScreen {
+        fn new(lines: &[&str]) -> Self {
+            let rows = lines
+                .iter()
+                .map(|line| {
+                    line.chars()
+                        .map(|c| MockCell {
+                            contents: if c == ' ' { String::new() } else { c.into() },
+                            ..MockCell::default()
+                        })
+                        .collect()
+                })
+                .collect();
+            Self {
+                rows,
+                cursor: (0, 0),
+                scroll_region: None,
+                saved_cursor: None,
+                origin_mode: false,
+                insert_mode: false,
+                echo_disabled: false,
+            }
+        }
+    }
+
+    impl Screen for MockScreen {
+        type C = MockCell;
+
+        fn cell(&self, row: u16, col: u16) -> Option<&Self::C> {
+            self.rows.get(row as usize)?.get(col as usize)
+        }
+
+        fn hide_cursor(&self) -> bool {
+            false
+        }
+
+        fn cursor_position(&self) -> (u16, u16) {
+            self.cursor
+        }
+
+        fn scroll_region(&self) -> (u16, u16) {
+            self.scroll_region
+                .unwrap_or((0, self.rows.len() as u16 - 1))
+        }
+
+        fn saved_cursor(&self) -> Option<(u16, u16)> {
+            self.saved_cursor
+        }
+
+        fn origin_mode(&self) -> bool {
+            self.origin_mode
+        }
+
+        fn insert_mode(&self) -> bool {
+            self.insert_mode
+        }
+
+        fn echo_disabled(&self) -> bool {
+            self.echo_disabled
+        }
+    }
+
+    impl Cell for MockCell {
+        fn has_contents(&self) -> bool {
+            !self.contents.is_empty()
+        }
+
+        fn apply(&self, cell: &mut ratatui::buffer::Cell) {
+            if self.has_contents() {
+                cell.set_symbol(&self.contents);
+            }
+        }
+
+        fn protected(&self) -> bool {
+            self.protected
+        }
+
+        fn charset(&self) -> Charset {
+            self.charset
+        }
+    }
+
+    fn snapshot_typescript(stream: &[u8]) -> String {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        parser.process(stream);
+        let pseudo_term = PseudoTerminal::new(parser.screen());
+        terminal
+            .draw(|f| {
+                f.render_widget(pseudo_term, f.area());
+            })
+            .unwrap();
+        format!("{:?}", terminal.backend().buffer())
+    }
+
+    fn render_to_buffer<S: Screen + ?Sized>(
+        pseudo_term: PseudoTerminal<'_, S>,
+        width: u16,
+        height: u16,
+    ) -> Buffer {
+        let backend = TestBackend::new(width, height);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|f| {
+                f.render_widget(pseudo_term, f.area());
+            })
+            .unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    #[test]
+    fn empty_actions() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        parser.process(b" ");
+        let pseudo_term = PseudoTerminal::new(parser.screen());
+        terminal
+            .draw(|f| {
+                f.render_widget(pseudo_term, f.area());
+            })
+            .unwrap();
+        let view = format!("{:?}", terminal.backend().buffer());
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn boundary_rows_overshot_no_panic() {
+        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
+        // Make the backend on purpose much smaller
+        let backend = TestBackend::new(80, 4);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        parser.process(stream);
+        let pseudo_term = PseudoTerminal::new(parser.screen());
+        terminal
+            .draw(|f| {
+                f.render_widget(pseudo_term, f.area());
+            })
+            .unwrap();
+        let view = format!("{:?}", terminal.backend().buffer());
+        insta::assert_snapshot!(view);
+    }
+
+    #[test]
+    fn simple_ls() {
+        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn simple_cursor_alternate_symbol() {
+        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        let cursor = Cursor::default().symbol("|");
+        parser.process(stream);
+        let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(cursor);
+        terminal
+            .draw(|f| {
+                f.render_widget(pseudo_term, f.area());
+            })
+            .unwrap();
+        let view = format!("{:?}", terminal.backend().buffer());
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn simple_cursor_styled() {
+        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        let style = Style::default().bg(Color::Cyan).fg(Color::LightRed);
+        let cursor = Cursor::default().symbol("|").style(style);
+        parser.process(stream);
+        let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(cursor);
+        terminal
+            .draw(|f| {
+                f.render_widget(pseudo_term, f.area());
+            })
+            .unwrap();
+        let view = format!("{:?}", terminal.backend().buffer());
+        insta::assert_snapshot!(view);
     }
-}
-
-#[cfg(all(test, feature = "vt100"))]
-mod tests {
-    use ratatui::{backend::TestBackend, widgets::Borders, Terminal};
-
-    use super::*;
-
-    fn snapshot_typescript(stream: &[u8]) -> String {
+    #[test]
+    fn simple_cursor_hide() {
+        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
         let backend = TestBackend::new(80, 24);
         let mut terminal = Terminal::new(backend).unwrap();
         let mut parser = vt100::Parser::new(24, 80, 0);
+        let cursor = Cursor::default().visibility(false);
         parser.process(stream);
-        let pseudo_term = PseudoTerminal::new(parser.screen());
+        let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(cursor);
         terminal
             .draw(|f| {
                 f.render_widget(pseudo_term, f.area());
             })
             .unwrap();
-        format!("{:?}", terminal.backend().buffer())
+        let view = format!("{:?}", terminal.backend().buffer());
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn simple_cursor_hide_alt() {
+        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        let mut cursor = Cursor::default();
+        cursor.hide();
+        parser.process(stream);
+        let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(cursor);
+        terminal
+            .draw(|f| {
+                f.render_widget(pseudo_term, f.area());
+            })
+            .unwrap();
+        let view = format!("{:?}", terminal.backend().buffer());
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn overlapping_cursor() {
+        let stream = include_bytes!("../test/typescript/overlapping_cursor.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn overlapping_cursor_alternate_style() {
+        let stream = include_bytes!("../test/typescript/overlapping_cursor.typescript");
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        let style = Style::default().bg(Color::Cyan).fg(Color::LightRed);
+        let cursor = Cursor::default().overlay_style(style);
+        parser.process(stream);
+        let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(cursor);
+        terminal
+            .draw(|f| {
+                f.render_widget(pseudo_term, f.area());
+            })
+            .unwrap();
+        let view = format!("{:?}", terminal.backend().buffer());
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn simple_ls_with_block() {
+        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
+        let backend = TestBackend::new(100, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        parser.process(stream);
+        let block = Block::default().borders(Borders::ALL).title("ls");
+        let pseudo_term = PseudoTerminal::new(parser.screen()).block(block);
+        terminal
+            .draw(|f| {
+                f.render_widget(pseudo_term, f.area());
+            })
+            .unwrap();
+        let view = format!("{:?}", terminal.backend().buffer());
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn simple_ls_no_style_from_block() {
+        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
+        let backend = TestBackend::new(100, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        parser.process(stream);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .title("ls");
+        let pseudo_term = PseudoTerminal::new(parser.screen()).block(block);
+        terminal
+            .draw(|f| {
+                f.render_widget(pseudo_term, f.area());
+            })
+            .unwrap();
+        let view = format!("{:?}", terminal.backend().buffer());
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn italic_text() {
+        let stream = b"[3mThis line will be displayed in italic.[0m This should have no style.";
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn underlined_text() {
+        let stream =
+            b"[4mThis line will be displayed with an underline.[0m This should have no style.";
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn bold_text() {
+        let stream = b"[1mThis line will be displayed bold.[0m This should have no style.";
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn inverse_text() {
+        let stream = b"[7mThis line will be displayed inversed.[0m This should have no style.";
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn combined_modifier_text() {
+        let stream =
+            b"[4m[3mThis line will be displayed in italic and underlined.[0m This should have no style.";
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+
+    #[test]
+    fn vttest_02_01() {
+        let stream = include_bytes!("../test/typescript/vttest_02_01.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn vttest_02_02() {
+        let stream = include_bytes!("../test/typescript/vttest_02_02.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn vttest_02_03() {
+        let stream = include_bytes!("../test/typescript/vttest_02_03.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn vttest_02_04() {
+        let stream = include_bytes!("../test/typescript/vttest_02_04.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn vttest_02_05() {
+        let stream = include_bytes!("../test/typescript/vttest_02_05.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn vttest_02_06() {
+        let stream = include_bytes!("../test/typescript/vttest_02_06.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn vttest_02_07() {
+        let stream = include_bytes!("../test/typescript/vttest_02_07.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn vttest_02_08() {
+        let stream = include_bytes!("../test/typescript/vttest_02_08.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn vttest_02_09() {
+        let stream = include_bytes!("../test/typescript/vttest_02_09.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn vttest_02_10() {
+        let stream = include_bytes!("../test/typescript/vttest_02_10.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn vttest_02_11() {
+        let stream = include_bytes!("../test/typescript/vttest_02_11.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn vttest_02_12() {
+        let stream = include_bytes!("../test/typescript/vttest_02_12.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn vttest_02_13() {
+        let stream = include_bytes!("../test/typescript/vttest_02_13.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn vttest_02_14() {
+        let stream = include_bytes!("../test/typescript/vttest_02_14.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
+    }
+    #[test]
+    fn vttest_02_15() {
+        let stream = include_bytes!("../test/typescript/vttest_02_15.typescript");
+        let view = snapshot_typescript(stream);
+        insta::assert_snapshot!(view);
     }
 
     #[test]
-    fn empty_actions() {
+    fn from_boxed_dyn_screen() {
+        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        parser.process(stream);
+        let screens: Vec<Box<dyn DynScreen>> = vec![Box::new(parser.screen().clone())];
+
         let backend = TestBackend::new(80, 24);
         let mut terminal = Terminal::new(backend).unwrap();
-        let mut parser = vt100::Parser::new(24, 80, 0);
-        parser.process(b" ");
-        let pseudo_term = PseudoTerminal::new(parser.screen());
+        let pseudo_term = PseudoTerminal::from_dyn(screens[0].as_ref());
         terminal
             .draw(|f| {
                 f.render_widget(pseudo_term, f.area());
             })
             .unwrap();
         let view = format!("{:?}", terminal.backend().buffer());
-        insta::assert_snapshot!(view);
+        assert_eq!(view, snapshot_typescript(stream));
     }
+
     #[test]
-    fn boundary_rows_overshot_no_panic() {
+    fn debug_grid_rulers() {
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        parser.process(b"hello");
+        let pseudo_term = PseudoTerminal::new(parser.screen()).debug_grid(true);
+        let buf = render_to_buffer(pseudo_term, 80, 24);
+        for col in [10, 20, 30, 70] {
+            assert_eq!(buf[(col, 5)].symbol(), "┊");
+            assert!(buf[(col, 5)].modifier.contains(Modifier::DIM));
+        }
+        assert_eq!(buf[(5, 10)].symbol(), "┈");
+        assert_eq!(buf[(10, 10)].symbol(), "┼");
+        assert_eq!(buf[(9, 5)].symbol(), " ");
+        // Content is dimmed, not replaced
+        assert_eq!(buf[(0, 0)].symbol(), "h");
+    }
+
+    #[test]
+    fn cursor_visibility_precedence() {
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        parser.process(b"$ ");
+        let visible = Cursor::default().symbol("|");
+        let cursor_drawn = |parser: &vt100::Parser, cursor: Cursor| {
+            let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(cursor);
+            render_to_buffer(pseudo_term, 80, 24)[(2, 0)].symbol() == "|"
+        };
+
+        // screen shows + user shows
+        assert!(cursor_drawn(&parser, visible.clone()));
+        // screen shows + user hides
+        assert!(!cursor_drawn(&parser, visible.clone().visibility(false)));
+
+        parser.process(b"\x1b[?25l");
+        // screen hides + user shows: the screen wins
+        assert!(!cursor_drawn(&parser, visible.clone()));
+        // screen hides + force_show
+        assert!(cursor_drawn(&parser, visible.clone().force_show(true)));
+        // screen hides + user hides + force_show: the user wins
+        assert!(!cursor_drawn(
+            &parser,
+            visible.force_show(true).visibility(false)
+        ));
+    }
+
+    #[test]
+    fn separate_cursor_pass() {
         let stream = include_bytes!("../test/typescript/simple_ls.typescript");
-        // Make the backend on purpose much smaller
-        let backend = TestBackend::new(80, 4);
-        let mut terminal = Terminal::new(backend).unwrap();
         let mut parser = vt100::Parser::new(24, 80, 0);
         parser.process(stream);
+        let block = Block::default().borders(Borders::ALL).title("ls");
+        let combined = render_to_buffer(
+            PseudoTerminal::new(parser.screen()).block(block.clone()),
+            100,
+            26,
+        );
+
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .block(block.clone())
+            .render_cursor(false);
+        let mut buf = render_to_buffer(pseudo_term, 100, 26);
+        assert_ne!(buf, combined);
+
+        let pseudo_term = PseudoTerminal::new(parser.screen()).block(block);
+        pseudo_term.draw_cursor(buf.area, &mut buf);
+        assert_eq!(buf, combined);
+    }
+
+    #[test]
+    fn transparent_color_passes_through() {
+        let mut parser = vt100::Parser::new(4, 10, 0);
+        parser.process(b"\x1b[48;2;0;255;0mAB\x1b[0mCD");
+        let pseudo_term =
+            PseudoTerminal::new(parser.screen()).transparent_color(Color::Rgb(0, 255, 0));
+        let area = Rect::new(0, 0, 10, 4);
+        let mut buf = Buffer::empty(area);
+        buf.set_string(0, 0, "xxxxxxxxxx", Style::default().bg(Color::Blue));
+        pseudo_term.render(area, &mut buf);
+
+        assert_eq!(buf[(0, 0)].symbol(), "x");
+        assert_eq!(buf[(1, 0)].bg, Color::Blue);
+        assert_eq!(buf[(2, 0)].symbol(), "C");
+        assert_eq!(buf[(3, 0)].bg, Color::Reset);
+        assert_eq!(buf[(9, 0)].symbol(), " ");
+        assert_eq!(buf[(9, 0)].bg, Color::Reset);
+    }
+
+    #[test]
+    fn cursor_on_last_column_stays_in_area() {
+        let mut parser = vt100::Parser::new(2, 5, 0);
+        parser.process("abc世".as_bytes());
+
+        let area = Rect::new(2, 0, 5, 2);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 2));
+        buf.set_string(7, 0, "xxx", Style::default());
+        PseudoTerminal::new(parser.screen()).render(area, &mut buf);
+        for col in 7..10 {
+            assert_eq!(buf[(col, 0)].symbol(), "x");
+        }
+
+        // An area larger than the buffer must not panic
         let pseudo_term = PseudoTerminal::new(parser.screen());
-        terminal
-            .draw(|f| {
-                f.render_widget(pseudo_term, f.area());
-            })
-            .unwrap();
-        let view = format!("{:?}", terminal.backend().buffer());
-        insta::assert_snapshot!(view);
+        pseudo_term.draw_cursor(Rect::new(8, 0, 5, 2), &mut buf);
+        assert_eq!(buf[(9, 0)].symbol(), "x");
     }
 
     #[test]
-    fn simple_ls() {
-        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn protected_cells_overlay() {
+        let mut screen = MockScreen::new(&["name: ____", "      ok  "]);
+        for cell in &mut screen.rows[0][..6] {
+            cell.protected = true;
+        }
+        screen.cursor = (1, 9);
+        let style = Style::default().bg(Color::DarkGray);
+
+        let buf = render_to_buffer(PseudoTerminal::new(&screen), 10, 2);
+        assert_eq!(buf[(0, 0)].bg, Color::Reset);
+
+        let pseudo_term = PseudoTerminal::new(&screen).protected_style(Some(style));
+        let buf = render_to_buffer(pseudo_term, 10, 2);
+        assert_eq!(buf[(0, 0)].symbol(), "n");
+        for col in 0..6 {
+            assert_eq!(buf[(col, 0)].bg, Color::DarkGray);
+        }
+        for col in 6..10 {
+            assert_eq!(buf[(col, 0)].bg, Color::Reset);
+        }
+        assert_eq!(buf[(6, 1)].bg, Color::Reset);
     }
+
     #[test]
-    fn simple_cursor_alternate_symbol() {
-        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
-        let backend = TestBackend::new(80, 24);
-        let mut terminal = Terminal::new(backend).unwrap();
+    fn content_bounds_top_left() {
         let mut parser = vt100::Parser::new(24, 80, 0);
-        let cursor = Cursor::default().symbol("|");
-        parser.process(stream);
-        let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(cursor);
-        terminal
-            .draw(|f| {
-                f.render_widget(pseudo_term, f.area());
-            })
-            .unwrap();
-        let view = format!("{:?}", terminal.backend().buffer());
+        assert_eq!(
+            PseudoTerminal::new(parser.screen()).content_bounds(),
+            Rect::default()
+        );
+        parser.process(b"ab\r\n  cdef\r\nx");
+        let pseudo_term = PseudoTerminal::new(parser.screen());
+        assert_eq!(pseudo_term.content_bounds(), Rect::new(0, 0, 6, 3));
+
+        let screen = MockScreen::new(&["     ", "  x  ", "   y ", "     "]);
+        assert_eq!(screen.size(), (4, 5));
+        let pseudo_term = PseudoTerminal::new(&screen);
+        assert_eq!(pseudo_term.content_bounds(), Rect::new(2, 1, 2, 2));
+    }
+
+    #[test]
+    fn plain_text_trimmed_and_padded() {
+        let mut parser = vt100::Parser::new(4, 8, 0);
+        parser.process(b"ab  \r\n c");
+        let pseudo_term = PseudoTerminal::new(parser.screen());
+
+        assert_eq!(pseudo_term.to_plain_text(), "ab\n c");
+
+        let padded = TextExportOptions::default().pad_to_width(true);
+        assert_eq!(
+            pseudo_term.to_plain_text_with(&padded),
+            "ab      \n c      "
+        );
+
+        let raw = TextExportOptions::default()
+            .trim_trailing(false)
+            .pad_to_width(true);
+        assert_eq!(
+            pseudo_term.to_plain_text_with(&raw),
+            "ab      \n c      \n        \n        "
+        );
+
+        let marked = TextExportOptions::default().include_cursor_marker(true);
+        assert_eq!(pseudo_term.to_plain_text_with(&marked), "ab\n c█");
+
+        // The marker replaces a wide character together with its continuation cell
+        let mut parser = vt100::Parser::new(2, 8, 0);
+        parser.process("ab\r\n 字d\x1b[2;2H".as_bytes());
+        let pseudo_term = PseudoTerminal::new(parser.screen());
+        assert_eq!(pseudo_term.to_plain_text_with(&marked), "ab\n █d");
+    }
+
+    #[test]
+    fn linedraw_export_modes() {
+        let mut screen = MockScreen::new(&["┌──┐ lqqk", "│ab│ x  x", "└──┘ mqqj"]);
+        for row in &mut screen.rows {
+            for cell in row.iter_mut() {
+                cell.charset = Charset::DecSpecialGraphics;
+            }
+        }
+        screen.rows[1][1].charset = Charset::Ascii;
+        screen.rows[1][2].charset = Charset::Ascii;
+        let pseudo_term = PseudoTerminal::new(&screen);
+
+        assert_eq!(
+            pseudo_term.to_plain_text(),
+            "┌──┐ lqqk\n│ab│ x  x\n└──┘ mqqj"
+        );
+        let options = TextExportOptions::default().ascii_linedraw(true);
+        assert_eq!(
+            pseudo_term.to_plain_text_with(&options),
+            "+--+ +--+\n|ab| |  |\n+--+ +--+"
+        );
+        let options = TextExportOptions::default().linedraw(LinedrawMode::Unicode);
+        assert_eq!(
+            pseudo_term.to_plain_text_with(&options),
+            "┌──┐ ┌──┐\n│ab│ │  │\n└──┘ └──┘"
+        );
+        let options = TextExportOptions::default().linedraw(LinedrawMode::Raw);
+        assert_eq!(
+            pseudo_term.to_plain_text_with(&options),
+            "┌──┐ lqqk\n│ab│ x  x\n└──┘ mqqj"
+        );
+    }
+
+    #[test]
+    fn cursor_line_highlight() {
+        let mut parser = vt100::Parser::new(4, 10, 0);
+        parser.process(b"first\r\n$ ls");
+        let style = Style::default().bg(Color::DarkGray);
+        let pseudo_term = PseudoTerminal::new(parser.screen()).cursor_line_style(Some(style));
+        let buf = render_to_buffer(pseudo_term, 12, 4);
+
+        for col in 0..12 {
+            if col != 4 {
+                assert_eq!(buf[(col, 1)].bg, Color::DarkGray, "column {col}");
+            }
+            assert_eq!(buf[(col, 0)].bg, Color::Reset);
+            assert_eq!(buf[(col, 2)].bg, Color::Reset);
+        }
+        assert_eq!(buf[(2, 1)].symbol(), "l");
+        // The cursor is drawn on top of the line highlight
+        assert_eq!(buf[(4, 1)].symbol(), "█");
+        assert_eq!(buf[(4, 1)].fg, Color::Gray);
+    }
+
+    #[test]
+    fn cursor_column_highlight() {
+        let mut parser = vt100::Parser::new(4, 10, 0);
+        parser.process(b"a  b\r\nccc");
+        let column = Style::default().bg(Color::Blue);
+        let pseudo_term = PseudoTerminal::new(parser.screen()).cursor_column_style(Some(column));
+        let buf = render_to_buffer(pseudo_term, 10, 4);
+
+        for row in [0, 2, 3] {
+            assert_eq!(buf[(3, row)].bg, Color::Blue, "row {row}");
+            assert_eq!(buf[(2, row)].bg, Color::Reset);
+        }
+        assert_eq!(buf[(3, 0)].symbol(), "b");
+
+        let line = Style::default().bg(Color::DarkGray);
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .cursor_column_style(Some(column))
+            .cursor_line_style(Some(line))
+            .cursor(Cursor::default().visibility(false));
+        let buf = render_to_buffer(pseudo_term, 10, 4);
+        // The line highlight wins at the intersection
+        assert_eq!(buf[(3, 1)].bg, Color::DarkGray);
+        assert_eq!(buf[(3, 0)].bg, Color::Blue);
+    }
+
+    #[test]
+    fn wrapped_rows() {
+        let mut parser = vt100::Parser::new(4, 5, 0);
+        parser.process(b"abcdefg\r\nhi");
+        let screen = parser.screen();
+        assert!(Screen::row_wrapped(screen, 0));
+        assert!(!Screen::row_wrapped(screen, 1));
+        assert!(!Screen::row_wrapped(screen, 2));
+
+        let boxed: Box<dyn DynScreen> = Box::new(screen.clone());
+        assert!(boxed.as_ref().row_wrapped(0));
+    }
+
+    #[test]
+    fn overlay_in_screen_coordinates() {
+        let mut parser = vt100::Parser::new(10, 20, 0);
+        parser.process(b"content");
+        let block = Block::default().borders(Borders::ALL);
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .block(block)
+            .overlay(
+                Rect::new(2, 2, 4, 1),
+                ratatui::widgets::Paragraph::new("tip!"),
+            )
+            // Clipped to the inner area
+            .overlay(
+                Rect::new(18, 8, 10, 1),
+                ratatui::widgets::Paragraph::new("clipped"),
+            );
+        let buf = render_to_buffer(pseudo_term, 22, 12);
+
+        assert_eq!(buf[(1, 1)].symbol(), "c");
+        let text: String = (3..7).map(|x| buf[(x, 3)].symbol()).collect();
+        assert_eq!(text, "tip!");
+        assert_eq!(buf[(19, 9)].symbol(), "c");
+        assert_eq!(buf[(20, 9)].symbol(), "l");
+        assert_eq!(buf[(21, 9)].symbol(), "│");
+    }
+
+    #[test]
+    fn scale_to_fit_preview() {
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        let row = "x".repeat(80);
+        for _ in 0..23 {
+            parser.process(row.as_bytes());
+            parser.process(b"\r\n");
+        }
+        parser.process(&row.as_bytes()[..79]);
+        let without_cursor = Cursor::default().visibility(false);
+
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .scale_to_fit(true)
+            .cursor(without_cursor.clone());
+        let buf = render_to_buffer(pseudo_term, 20, 6);
+        assert!(buf.content.iter().all(|cell| cell.symbol() == "x"));
+
+        // 24x80 sampled in blocks of 3x3 results in a 8x27 preview
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .scale_to_fit(true)
+            .cursor(without_cursor);
+        let buf = render_to_buffer(pseudo_term, 30, 10);
+        assert_eq!(buf[(26, 7)].symbol(), "x");
+        assert_eq!(buf[(27, 7)].symbol(), " ");
+        assert_eq!(buf[(26, 8)].symbol(), " ");
+    }
+
+    #[test]
+    fn no_modifier_bleed_into_shared_buffer() {
+        let mut parser = vt100::Parser::new(2, 4, 0);
+        parser.process(b"\x1b[1mab\x1b[0m");
+        let seeded = Style::default()
+            .bg(Color::Blue)
+            .add_modifier(Modifier::ITALIC | Modifier::UNDERLINED);
+        let area = Rect::new(0, 0, 6, 2);
+        let without_cursor = Cursor::default().visibility(false);
+
+        let mut buf = Buffer::filled(area, ratatui::buffer::Cell::new("z"));
+        buf.set_style(area, seeded);
+        PseudoTerminal::new(parser.screen())
+            .cursor(without_cursor.clone())
+            .render(area, &mut buf);
+        assert!(buf.content.iter().all(|cell| cell.bg == Color::Reset));
+        assert_eq!(buf[(0, 0)].modifier, Modifier::BOLD);
+        assert!(buf
+            .content
+            .iter()
+            .filter(|cell| cell.symbol() != "a" && cell.symbol() != "b")
+            .all(|cell| cell.symbol() == " " && cell.modifier.is_empty()));
+
+        // Without the reset, cells drawn from the screen still don't inherit anything
+        let mut buf = Buffer::filled(area, ratatui::buffer::Cell::new("z"));
+        buf.set_style(area, seeded);
+        PseudoTerminal::new(parser.screen())
+            .cursor(without_cursor)
+            .reset_area_first(false)
+            .render(area, &mut buf);
+        assert_eq!(buf[(1, 0)].modifier, Modifier::BOLD);
+        assert_eq!(buf[(2, 0)].symbol(), " ");
+        assert!(buf[(2, 0)].modifier.is_empty());
+        assert_eq!(buf[(2, 0)].bg, Color::Reset);
+        // The cells the screen doesn't cover are kept
+        assert_eq!(buf[(5, 1)].symbol(), "z");
+        assert_eq!(buf[(5, 1)].bg, Color::Blue);
+    }
+
+    #[test]
+    fn ligature_hints_merge_pairs() {
+        let mut parser = vt100::Parser::new(2, 10, 0);
+        parser.process(b"a != b\x1b[1m-\x1b[0m>");
+        let without_cursor = Cursor::default().visibility(false);
+
+        let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(without_cursor.clone());
+        let buf = render_to_buffer(pseudo_term, 10, 2);
+        assert_eq!(buf[(2, 0)].symbol(), "!");
+        assert_eq!(buf[(3, 0)].symbol(), "=");
+
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .cursor(without_cursor)
+            .ligature_hints(true);
+        let buf = render_to_buffer(pseudo_term, 10, 2);
+        assert_eq!(buf[(2, 0)].symbol(), "!=");
+        assert_eq!(buf[(3, 0)].symbol(), " ");
+        assert_eq!(buf[(4, 0)].symbol(), " ");
+        // The style changes within the pair, so it isn't merged
+        assert_eq!(buf[(6, 0)].symbol(), "-");
+        assert_eq!(buf[(7, 0)].symbol(), ">");
+    }
+
+    #[test]
+    fn update_cursor_only_touches_two_cells() {
+        let mut parser = vt100::Parser::new(5, 10, 0);
+        parser.process(b"hello\r\nworld");
+        let area = Rect::new(0, 0, 12, 7);
+        let block = Block::default().borders(Borders::ALL);
+        let mut buf = Buffer::empty(area);
+        PseudoTerminal::new(parser.screen())
+            .block(block.clone())
+            .render(area, &mut buf);
+
+        let prev_pos = parser.screen().cursor_position();
+        parser.process(b"\x1b[1;2H");
+        let before = buf.clone();
+        PseudoTerminal::new(parser.screen())
+            .block(block.clone())
+            .update_cursor_only(prev_pos, area, &mut buf);
+
+        let changed: Vec<_> = (0..area.height)
+            .flat_map(|y| (0..area.width).map(move |x| (x, y)))
+            .filter(|&pos| buf[pos] != before[pos])
+            .collect();
+        assert_eq!(changed, vec![(2, 1), (6, 2)]);
+
+        let mut expected = Buffer::empty(area);
+        PseudoTerminal::new(parser.screen())
+            .block(block)
+            .render(area, &mut expected);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn scroll_region_margins() {
+        let parser = vt100::Parser::new(24, 80, 0);
+        assert_eq!(parser.screen().scroll_region(), (0, 23));
+
+        let mut screen = MockScreen::new(&["", "", "", "", ""]);
+        screen.scroll_region = Some((1, 3));
+        let dyn_screen: &dyn DynScreen = &screen;
+        assert_eq!(dyn_screen.scroll_region(), (1, 3));
+
+        let pseudo_term = PseudoTerminal::new(&screen)
+            .cursor(Cursor::default().visibility(false))
+            .debug_scroll_region(true);
+        let buf = render_to_buffer(pseudo_term, 4, 5);
+        let markers: Vec<_> = (0..5).map(|row| buf[(3, row)].symbol()).collect();
+        assert_eq!(markers, [" ", "┐", "│", "┘", " "]);
+    }
+
+    #[test]
+    fn absolute_coords_across_scrollback() {
+        let mut parser = vt100::Parser::new(4, 10, 20);
+        for line in 0..10 {
+            parser.process(format!("line {line}\r\n").as_bytes());
+        }
+        let area = Rect::new(5, 2, 12, 6);
+        let block = Block::default().borders(Borders::ALL);
+        let mut state = PseudoTerminalState::new();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 10));
+
+        ratatui::widgets::StatefulWidget::render(
+            PseudoTerminal::new(parser.screen()).block(block.clone()),
+            area,
+            &mut buf,
+            &mut state,
+        );
+        assert_eq!(state.area(), Rect::new(6, 3, 10, 4));
+        // Live region without scrolling
+        assert_eq!(state.absolute_coords(3, 6), (0, 0));
+        assert_eq!(state.absolute_coords(6, 9), (3, 3));
+
+        for offset in [1, 3, 4] {
+            parser.set_scrollback(offset);
+            state.set_scroll_offset(parser.screen().scrollback());
+            ratatui::widgets::StatefulWidget::render(
+                PseudoTerminal::new(parser.screen()).block(block.clone()),
+                area,
+                &mut buf,
+                &mut state,
+            );
+            let offset = offset as isize;
+            // The top row shows the scrollback, the bottom row is live until scrolled a page
+            assert_eq!(state.absolute_coords(3, 7), (-offset, 1));
+            assert_eq!(state.absolute_coords(6, 7), (3 - offset, 1));
+        }
+        assert!(state.absolute_coords(6, 7).0 < 0);
+
+        // The offset is only clamped on render
+        state.scroll_right(u16::MAX);
+        assert_eq!(state.absolute_coords(3, 7).1, u16::MAX);
+    }
+
+    #[test]
+    fn shadow_inside_area() {
+        let mut parser = vt100::Parser::new(3, 6, 0);
+        parser.process(b"hi");
+        let shadow = Style::default().bg(Color::DarkGray);
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .block(Block::default().borders(Borders::ALL))
+            .cursor(Cursor::default().visibility(false))
+            .shadow(Some(shadow));
+        let buf = render_to_buffer(pseudo_term, 9, 6);
+
+        // The block shrinks by one row and column to make room for the shadow
+        assert_eq!(buf[(1, 1)].symbol(), "h");
+        assert_eq!(buf[(7, 2)].symbol(), "│");
+        assert_eq!(buf[(3, 4)].symbol(), "─");
+        assert_eq!(buf[(8, 2)].symbol(), " ");
+        assert_eq!(buf[(3, 5)].symbol(), " ");
+        let shadowed = |x: u16, y: u16| buf[(x, y)].bg == Color::DarkGray;
+        assert!((1..6).all(|y| shadowed(8, y)));
+        assert!((1..9).all(|x| shadowed(x, 5)));
+        assert!(!shadowed(8, 0));
+        assert!(!shadowed(0, 5));
+        assert!(!shadowed(7, 4));
+    }
+
+    #[test]
+    fn pan_long_lines() {
+        let mut parser = vt100::Parser::new(2, 20, 0);
+        parser.process(b"0123456789abcdefghij");
+        let area = Rect::new(0, 0, 8, 2);
+        let mut state = PseudoTerminalState::new();
+        let visible = |state: &mut PseudoTerminalState| {
+            let mut buf = Buffer::empty(area);
+            ratatui::widgets::StatefulWidget::render(
+                PseudoTerminal::new(parser.screen()).render_cursor(false),
+                area,
+                &mut buf,
+                state,
+            );
+            (0..8).map(|x| buf[(x, 0)].symbol()).collect::<String>()
+        };
+
+        assert_eq!(visible(&mut state), "01234567");
+        state.scroll_right(3);
+        assert_eq!(visible(&mut state), "3456789a");
+        assert_eq!(state.absolute_coords(0, 1), (0, 4));
+        // Panning stops at the last column of the screen
+        state.scroll_right(100);
+        assert_eq!(visible(&mut state), "cdefghij");
+        assert_eq!(state.h_offset(), 12);
+        state.scroll_left(10);
+        assert_eq!(visible(&mut state), "23456789");
+        state.scroll_left(10);
+        assert_eq!(state.h_offset(), 0);
+    }
+
+    #[test]
+    fn wide_characters_across_helpers() {
+        assert_eq!(cell_width("a"), 1);
+        assert_eq!(cell_width("字"), 2);
+        assert_eq!(cell_width("😀"), 2);
+
+        let mut parser = vt100::Parser::new(2, 6, 0);
+        parser.process(b"\xe5\xad\x97a\xf0\x9f\x98\x80b");
+        let screen = parser.screen();
+        // The continuation cells of wide characters don't add columns
+        assert_eq!(PseudoTerminal::new(screen).to_plain_text(), "字a😀b");
+        assert_eq!(
+            export::table_cells(screen, &[0, 3, 6]),
+            [["字a".to_string(), "😀b".to_string()]]
+        );
+
+        // A wide character at the edge of the area is not drawn
+        let pseudo_term = PseudoTerminal::new(screen).render_cursor(false);
+        let buf = render_to_buffer(pseudo_term, 4, 2);
+        let row: Vec<&str> = (0..4).map(|x| buf[(x, 0)].symbol()).collect();
+        assert_eq!(row, ["字", " ", "a", " "]);
+    }
+
+    #[test]
+    fn reflow_text_to_width() {
+        let mut parser = vt100::Parser::new(4, 40, 0);
+        parser.process(b"The quick brown fox jumps over the lazy dog and keeps running\r\n");
+        parser.process(b"abcdefghijklmnopqrstuvwxyz");
+        assert!(parser.screen().row_wrapped(0));
+        let text = PseudoTerminal::new(parser.screen()).reflow_text(20);
+        assert_eq!(
+            text,
+            "The quick brown fox\njumps over the lazy\ndog and keeps\nrunning\n\
+             abcdefghijklmnopqrst\nuvwxyz"
+        );
+    }
+
+    #[test]
+    fn braille_preview_dimensions() {
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        parser.process(b"ab\r\n c");
+        let pseudo_term = PseudoTerminal::new(parser.screen());
+        let preview = pseudo_term.braille_preview(Rect::new(0, 0, 20, 3));
+        assert_eq!(preview.lines.len(), 3);
+        assert!(preview.lines.iter().all(|line| line.width() == 20));
+        // The first dot covers "ab" and " c", the second column of dots is blank
+        assert_eq!(
+            preview.lines[0].to_string().chars().next(),
+            Some('\u{2801}')
+        );
+
+        // A small screen is not upscaled
+        let preview = pseudo_term.braille_preview(Rect::new(0, 0, 100, 100));
+        assert_eq!(preview.lines.len(), 6);
+        assert!(preview.lines.iter().all(|line| line.width() == 40));
+        assert_eq!(
+            preview.lines[0].to_string().chars().next(),
+            Some('\u{2819}')
+        );
+        assert!(pseudo_term
+            .braille_preview(Rect::default())
+            .lines
+            .is_empty());
+    }
+
+    #[test]
+    fn detect_plain_urls() {
+        let mut parser = vt100::Parser::new(3, 14, 0);
+        parser.process(b"a http://x.io\r\ngo https://ex.com/a b");
+        let urls = PseudoTerminal::new(parser.screen()).detect_urls();
+        assert_eq!(
+            urls,
+            [
+                (Rect::new(2, 0, 11, 1), "http://x.io".to_string()),
+                (Rect::new(3, 1, 11, 1), "https://ex.com/a".to_string()),
+                (Rect::new(0, 2, 5, 1), "https://ex.com/a".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn content_digest() {
+        let digest = |bytes: &[u8]| {
+            let mut parser = vt100::Parser::new(3, 10, 0);
+            parser.process(bytes);
+            let pseudo_term = PseudoTerminal::new(parser.screen());
+            pseudo_term.content_digest()
+        };
+        assert_eq!(digest(b"\x1b[31mred"), digest(b"\x1b[31mred"));
+        // The cursor position doesn't matter
+        assert_eq!(digest(b"\x1b[31mred"), digest(b"\x1b[31mred\x1b[3;1H"));
+        assert_ne!(digest(b"\x1b[31mred"), digest(b"\x1b[31mrad"));
+        assert_ne!(digest(b"\x1b[31mred"), digest(b"\x1b[32mred"));
+    }
+
+    #[test]
+    fn render_dirty_cells_only() {
+        let mut parser = vt100::Parser::new(2, 4, 0);
+        let area = Rect::new(0, 0, 4, 2);
+        let mut buf = Buffer::empty(area);
+        PseudoTerminal::new(parser.screen())
+            .render_cursor(false)
+            .render(area, &mut buf);
+        parser.process(b"abcd\r\nefgh");
+        PseudoTerminal::new(parser.screen()).render_dirty(
+            &[(0, 1), (1, 0), (1, 3), (5, 5)],
+            area,
+            &mut buf,
+        );
+        let rows: Vec<String> = (0..2)
+            .map(|y| (0..4).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert_eq!(rows, [" b  ", "e  h"]);
+    }
+
+    #[test]
+    fn tab_width_in_text_export() {
+        let mut parser = vt100::Parser::new(2, 20, 0);
+        // Tab stops are every 8 columns
+        parser.process(b"a\tb\r\nabcdef g\tc");
+        let export = |tab_width: u16| {
+            PseudoTerminal::new(parser.screen())
+                .tab_width(tab_width)
+                .to_plain_text()
+        };
+        assert_eq!(export(8), "a\tb\nabcdef g\tc");
+        assert_eq!(export(4), "a\t\tb\nabcdef g\t\tc");
+        assert_eq!(
+            PseudoTerminal::new(parser.screen()).to_plain_text(),
+            "a       b\nabcdef g        c"
+        );
+    }
+
+    #[test]
+    fn dominant_background_color() {
+        let mut parser = vt100::Parser::new(2, 4, 0);
+        parser.process(b"\x1b[40m    \r\n  \x1b[41m  ");
+        let pseudo_term = PseudoTerminal::new(parser.screen());
+        assert_eq!(pseudo_term.dominant_background(), Color::Rgb(0, 0, 0));
+
+        let parser = vt100::Parser::new(2, 4, 0);
+        let pseudo_term = PseudoTerminal::new(parser.screen());
+        assert_eq!(pseudo_term.dominant_background(), Color::Reset);
+    }
+
+    #[test]
+    fn on_row_hook() {
+        let mut parser = vt100::Parser::new(4, 5, 0);
+        parser.process(b"one\r\ntwo\r\nthree");
+        let mut rows = Vec::new();
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .render_cursor(false)
+            .on_row(|row, cells| {
+                let text: String = cells.iter().map(CellInfo::symbol).collect();
+                rows.push((row, text));
+            });
+        // The area is shorter than the screen
+        render_to_buffer(pseudo_term, 5, 3);
+        assert_eq!(
+            rows,
+            [
+                (0, "one  ".to_string()),
+                (1, "two  ".to_string()),
+                (2, "three".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn clip_mask_keeps_cells() {
+        let mut parser = vt100::Parser::new(4, 8, 0);
+        parser.process(b"abcdefgh\r\nabcdefgh\r\nabcdefgh\r\nabcdefgh");
+        let hole = Rect::new(2, 1, 3, 2);
+        let area = Rect::new(0, 0, 8, 4);
+        let mut buf = Buffer::filled(area, ratatui::buffer::Cell::new("x"));
+        PseudoTerminal::new(parser.screen())
+            .clip_mask(|x, y| !hole.contains(ratatui::layout::Position::new(x, y)))
+            .render(area, &mut buf);
+        let rows: Vec<String> = (0..4)
+            .map(|y| (0..8).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert_eq!(rows, ["abcdefgh", "abxxxfgh", "abxxxfgh", "abcdefgh"]);
+    }
+
+    #[test]
+    fn inverse_mode_swaps_colors() {
+        let mut parser = vt100::Parser::new(1, 4, 0);
+        parser.process(b"\x1b[7;31;44mx\x1b[27my\x1b[0;7mz");
+        let render = |inverse_mode: InverseMode| {
+            let pseudo_term = PseudoTerminal::new(parser.screen())
+                .render_cursor(false)
+                .inverse_mode(inverse_mode);
+            render_to_buffer(pseudo_term, 4, 1)
+        };
+
+        let buf = render(InverseMode::Modifier);
+        assert_eq!(buf[(0, 0)].fg, Color::Indexed(1));
+        assert!(buf[(0, 0)].modifier.contains(Modifier::REVERSED));
+
+        let buf = render(InverseMode::SwapColors);
+        assert_eq!(
+            (buf[(0, 0)].fg, buf[(0, 0)].bg),
+            (Color::Indexed(4), Color::Indexed(1))
+        );
+        assert!(!buf[(0, 0)].modifier.contains(Modifier::REVERSED));
+        assert_eq!(
+            (buf[(1, 0)].fg, buf[(1, 0)].bg),
+            (Color::Indexed(1), Color::Indexed(4))
+        );
+        assert_eq!(
+            (buf[(2, 0)].fg, buf[(2, 0)].bg),
+            (Color::Black, Color::Gray)
+        );
+    }
+
+    #[test]
+    fn zebra_rows() {
+        let mut parser = vt100::Parser::new(3, 3, 0);
+        parser.process(b"a\r\n\x1b[41mb\x1b[0m\r\nc");
+        let view = PseudoTerminal::new(parser.screen())
+            .render_cursor(false)
+            .zebra(Some((
+                Style::default().bg(Color::Indexed(236)),
+                Style::default().bg(Color::Indexed(238)),
+            )))
+            .debug_render_string(Rect::new(0, 0, 3, 3));
         insta::assert_snapshot!(view);
     }
+
+    #[test]
+    fn debug_render_string_colors() {
+        let mut parser = vt100::Parser::new(2, 3, 0);
+        parser.process(b"\x1b[3;38;2;255;0;0;44ma\x1b[0m\r\n\x1b[38;5;200mb");
+        let rendered = PseudoTerminal::new(parser.screen())
+            .render_cursor(false)
+            .debug_render_string(Rect::new(0, 0, 3, 2));
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("a+#ff0000+Indexed4+ITALIC "));
+        assert!(lines[1].starts_with("b+Indexed200+Reset+- "));
+        assert!(lines[1].ends_with(" +Reset+Reset+-"));
+    }
+
+    #[test]
+    fn glyph_fallback_box_drawing() {
+        let mut parser = vt100::Parser::new(3, 4, 0);
+        parser.process("┌──┐\r\n│é │\r\n└──┘".as_bytes());
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .render_cursor(false)
+            .glyph_fallback(|glyph| match glyph {
+                "┌" | "┐" | "└" | "┘" => Some("+".to_string()),
+                "─" => Some("-".to_string()),
+                "│" => Some("|".to_string()),
+                _ => None,
+            });
+        let buf = render_to_buffer(pseudo_term, 4, 3);
+        let lines: Vec<String> = (0..3)
+            .map(|y| (0..4).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert_eq!(lines, ["+--+", "|é |", "+--+"]);
+    }
+
+    #[test]
+    fn symbol_map_substitutes() {
+        let mut parser = vt100::Parser::new(1, 4, 0);
+        parser.process("█a█".as_bytes());
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .render_cursor(false)
+            .symbol_map(&[('\u{2588}', '#'), ('a', 'b')]);
+        let buf = render_to_buffer(pseudo_term, 4, 1);
+        let line: String = (0..4).map(|x| buf[(x, 0)].symbol()).collect();
+        assert_eq!(line, "#b# ");
+    }
+
+    #[test]
+    fn mask_input_lock_indicator() {
+        let mut parser = vt100::Parser::new(2, 10, 0);
+        parser.process(b"Pass: ");
+        assert!(!Screen::echo_disabled(parser.screen()));
+        let has_lock = |buf: &Buffer| (0..10).any(|x| buf[(x, 0)].symbol() == LOCK_GLYPH);
+        let buf = render_to_buffer(PseudoTerminal::new(parser.screen()), 10, 2);
+        assert!(!has_lock(&buf));
+
+        let pseudo_term = PseudoTerminal::new(parser.screen()).mask_input(Some(Corner::TopRight));
+        let buf = render_to_buffer(pseudo_term, 10, 2);
+        assert!(has_lock(&buf));
+
+        // A status in another corner is kept
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .corner_status(Corner::TopRight, "x")
+            .mask_input(Some(Corner::BottomLeft));
+        let buf = render_to_buffer(pseudo_term, 10, 2);
+        assert!(!has_lock(&buf));
+        assert_eq!(buf[(9, 0)].symbol(), "x");
+        assert_eq!(buf[(0, 1)].symbol(), LOCK_GLYPH);
+        assert_eq!(buf[(4, 0)].symbol(), ":");
+
+        // A screen reporting disabled echo gets the lock without configuring it
+        let mut screen = MockScreen::new(&["Pass:", ""]);
+        let buf = render_to_buffer(PseudoTerminal::new(&screen), 10, 2);
+        assert!(!has_lock(&buf));
+        screen.echo_disabled = true;
+        let buf = render_to_buffer(PseudoTerminal::new(&screen), 10, 2);
+        assert!(has_lock(&buf));
+        let pseudo_term = PseudoTerminal::new(&screen).mask_input(Some(Corner::BottomLeft));
+        let buf = render_to_buffer(pseudo_term, 10, 2);
+        assert!(!has_lock(&buf));
+        assert_eq!(buf[(0, 1)].symbol(), LOCK_GLYPH);
+    }
+
+    #[test]
+    fn saved_cursor_position() {
+        // vt100 doesn't expose the saved cursor, ESC 7 / ESC 8 still move the cursor
+        let mut parser = vt100::Parser::new(5, 10, 0);
+        parser.process(b"\x1b[2;3H\x1b7\x1b[5;5H");
+        assert_eq!(parser.screen().saved_cursor(), None);
+        parser.process(b"\x1b8");
+        assert_eq!(parser.screen().cursor_position(), (1, 2));
+
+        let mut screen = MockScreen::new(&["", ""]);
+        screen.saved_cursor = Some((1, 2));
+        let dyn_screen: &dyn DynScreen = &screen;
+        assert_eq!(dyn_screen.saved_cursor(), Some((1, 2)));
+    }
+
+    #[test]
+    fn trim_trailing_blank_rows() {
+        let mut parser = vt100::Parser::new(24, 10, 0);
+        parser.process(b"1\r\n2\r\n3\r\n4\r\n5");
+        let area = Rect::new(0, 0, 10, 24);
+        let render = |trim: bool| {
+            let mut buf = Buffer::filled(area, ratatui::buffer::Cell::new("x"));
+            PseudoTerminal::new(parser.screen())
+                .trim_trailing_blank_rows(trim)
+                .render(area, &mut buf);
+            (0..24).filter(|&y| buf[(9, y)].symbol() != "x").count()
+        };
+        assert_eq!(render(false), 24);
+        assert_eq!(render(true), 5);
+    }
+
+    #[test]
+    fn peek_live_line_when_scrolled_up() {
+        let mut parser = vt100::Parser::new(3, 10, 10);
+        parser.process(b"1\r\n2\r\n3\r\n$ ls");
+        let area = Rect::new(0, 0, 10, 3);
+        let faint = Style::default().add_modifier(Modifier::DIM);
+        let mut state = PseudoTerminalState::new();
+        let render = |parser: &vt100::Parser, state: &mut PseudoTerminalState| {
+            let mut buf = Buffer::empty(area);
+            ratatui::widgets::StatefulWidget::render(
+                PseudoTerminal::new(parser.screen())
+                    .render_cursor(false)
+                    .peek_live_line(Some(faint)),
+                area,
+                &mut buf,
+                state,
+            );
+            buf
+        };
+
+        // At the bottom the live line is drawn as usual
+        let buf = render(&parser, &mut state);
+        assert_eq!(buf[(0, 2)].symbol(), "$");
+        assert!(!buf[(0, 2)].modifier.contains(Modifier::DIM));
+
+        parser.set_scrollback(2);
+        state.set_scroll_offset(parser.screen().scrollback());
+        let buf = render(&parser, &mut state);
+        let top_row: String = (0..10).map(|x| buf[(x, 0)].symbol()).collect();
+        assert_eq!(top_row, "1         ");
+        let bottom_row: String = (0..10).map(|x| buf[(x, 2)].symbol()).collect();
+        assert_eq!(bottom_row, "$ ls      ");
+        assert!(buf[(0, 2)].modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn invalidate_drops_previous_renders() {
+        let mut parser = vt100::Parser::new(3, 10, 10);
+        parser.process(b"1\r\n2\r\n$ ls");
+        let area = Rect::new(0, 0, 10, 3);
+        let mut state = PseudoTerminalState::new();
+        let render = |parser: &vt100::Parser, state: &mut PseudoTerminalState| {
+            let mut buf = Buffer::empty(area);
+            ratatui::widgets::StatefulWidget::render(
+                PseudoTerminal::new(parser.screen())
+                    .render_cursor(false)
+                    .peek_live_line(Some(Style::default()))
+                    .frozen(true),
+                area,
+                &mut buf,
+                state,
+            );
+            buf
+        };
+        let frozen = render(&parser, &mut state);
+        assert!(!state.live_line.is_empty());
+        parser.process(b"\r\n$ pwd");
+        assert_eq!(render(&parser, &mut state), frozen);
+
+        state.invalidate();
+        assert_eq!(state.area(), Rect::default());
+        assert!(state.live_line.is_empty());
+        // The frozen frame is captured again from the current screen
+        let buf = render(&parser, &mut state);
+        assert_ne!(buf, frozen);
+        assert_eq!(buf[(2, 2)].symbol(), "p");
+        assert_eq!(state.area(), area);
+    }
+
+    #[test]
+    fn scroll_fade_when_scrolled_up() {
+        let mut parser = vt100::Parser::new(3, 4, 10);
+        parser.process(b"\x1b[38;2;200;200;200ma\x1b[0m\r\nb\r\nc\r\nd");
+        let area = Rect::new(0, 0, 4, 3);
+        let mut state = PseudoTerminalState::new();
+        let render = |parser: &vt100::Parser, state: &mut PseudoTerminalState| {
+            let mut buf = Buffer::empty(area);
+            ratatui::widgets::StatefulWidget::render(
+                PseudoTerminal::new(parser.screen())
+                    .render_cursor(false)
+                    .scroll_fade(Some(2)),
+                area,
+                &mut buf,
+                state,
+            );
+            buf
+        };
+
+        let buf = render(&parser, &mut state);
+        assert_eq!(buf[(0, 0)].fg, Color::Reset);
+        assert!(!buf[(0, 0)].modifier.contains(Modifier::DIM));
+
+        parser.set_scrollback(1);
+        state.set_scroll_offset(parser.screen().scrollback());
+        let buf = render(&parser, &mut state);
+        assert_eq!(buf[(0, 0)].symbol(), "a");
+        // The top row is faded the most, towards the black default background
+        assert_eq!(buf[(0, 0)].fg, Color::Rgb(67, 67, 67));
+        assert!(buf[(0, 1)].modifier.contains(Modifier::DIM));
+        assert!(!buf[(0, 2)].modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn new_output_indicator_when_scrolled_up() {
+        let mut parser = vt100::Parser::new(3, 10, 10);
+        parser.process(b"1\r\n2\r\n3\r\n4");
+        let area = Rect::new(0, 0, 10, 3);
+        let mut state = PseudoTerminalState::new();
+        let bottom_row = |parser: &vt100::Parser, state: &mut PseudoTerminalState| {
+            let mut buf = Buffer::empty(area);
+            ratatui::widgets::StatefulWidget::render(
+                PseudoTerminal::new(parser.screen())
+                    .render_cursor(false)
+                    .new_output_indicator(Some("more")),
+                area,
+                &mut buf,
+                state,
+            );
+            (0..10).map(|x| buf[(x, 2)].symbol()).collect::<String>()
+        };
+
+        // New output at the bottom doesn't need an indicator
+        state.notify_new_output();
+        assert_eq!(bottom_row(&parser, &mut state), "4         ");
+
+        parser.set_scrollback(1);
+        state.set_scroll_offset(parser.screen().scrollback());
+        assert!(!state.at_bottom());
+        assert_eq!(bottom_row(&parser, &mut state), "3         ");
+        parser.process(b"\r\n5");
+        state.notify_new_output();
+        assert_eq!(&bottom_row(&parser, &mut state)[3..7], "more");
+
+        parser.set_scrollback(0);
+        state.set_scroll_offset(parser.screen().scrollback());
+        assert_eq!(bottom_row(&parser, &mut state), "5         ");
+    }
+
+    #[test]
+    fn style_under_cursor() {
+        let mut parser = vt100::Parser::new(2, 10, 0);
+        parser.process(b"\x1b[3;38;2;0;128;255mab\x1b[0m");
+        assert_eq!(
+            PseudoTerminal::new(parser.screen()).cursor_style(),
+            Style::default()
+        );
+
+        parser.process(b"\x1b[1D");
+        let style = PseudoTerminal::new(parser.screen()).cursor_style();
+        assert_eq!(style.fg, Some(Color::Rgb(0, 128, 255)));
+        assert_eq!(style.bg, Some(Color::Reset));
+        assert!(style.add_modifier.contains(Modifier::ITALIC));
+        assert!(!style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn auto_title_style_fn() {
+        let mut parser = vt100::Parser::new(3, 10, 0);
+        parser.process(b"\x1b]2;make\x07");
+        assert_eq!(Screen::title(parser.screen()), "make");
+        let render = |parser: &vt100::Parser| {
+            let pseudo_term = PseudoTerminal::new(parser.screen())
+                .block(Block::default().borders(Borders::ALL))
+                .auto_title(true)
+                .title_style_fn(|title| {
+                    let color = if title.contains("failed") {
+                        Color::Red
+                    } else {
+                        Color::Green
+                    };
+                    Style::default().fg(color)
+                });
+            render_to_buffer(pseudo_term, 14, 5)
+        };
+
+        let buf = render(&parser);
+        let top: String = (1..5).map(|x| buf[(x, 0)].symbol()).collect();
+        assert_eq!(top, "make");
+        assert_eq!(buf[(1, 0)].fg, Color::Green);
+
+        parser.process(b"\x1b]2;make failed\x07");
+        let buf = render(&parser);
+        assert_eq!(buf[(1, 0)].fg, Color::Red);
+    }
+
+    #[test]
+    fn corner_status_top_right() {
+        let mut parser = vt100::Parser::new(3, 10, 0);
+        parser.process(b"hello");
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .block(Block::default().borders(Borders::ALL))
+            .cursor(Cursor::default().visibility(false))
+            .corner_status(Corner::TopRight, "0:42")
+            .corner_status(Corner::BottomLeft, "x");
+        let buf = render_to_buffer(pseudo_term, 12, 5);
+        let row = |y: u16| (0..12).map(|x| buf[(x, y)].symbol()).collect::<String>();
+        assert_eq!(row(1), "│hello 0:42│");
+        assert_eq!(row(3), "│x         │");
+    }
+
+    #[test]
+    fn cursor_visibility_follows_alternate_screen() {
+        let mut parser = vt100::Parser::new(3, 10, 0);
+        let frames: [(&[u8], bool); 5] = [
+            (b"$ ", true),
+            (b"\x1b[?1049h\x1b[?25l", false),
+            (b"\x1b[?25h", true),
+            (b"\x1b[?25l\x1b[?1049l", false),
+            (b"\x1b[?25h", true),
+        ];
+        for (bytes, visible) in frames {
+            parser.process(bytes);
+            let screen = parser.screen();
+            assert_eq!(screen.hide_cursor(), !visible);
+            let (row, col) = screen.cursor_position();
+            let buf = render_to_buffer(PseudoTerminal::new(screen), 10, 3);
+            let drawn = buf[(col, row)].symbol() == "\u{2588}";
+            assert_eq!(drawn, visible, "after {bytes:?}");
+        }
+    }
+
+    #[test]
+    fn cursor_at_line_start() {
+        let mut parser = vt100::Parser::new(3, 10, 0);
+        parser.process(b"$ ls");
+        assert!(!PseudoTerminal::new(parser.screen()).cursor_at_line_start());
+        parser.process(b"\r\n");
+        assert!(PseudoTerminal::new(parser.screen()).cursor_at_line_start());
+        // At the start of a line with leftover output
+        parser.process(b"old\r");
+        assert!(!PseudoTerminal::new(parser.screen()).cursor_at_line_start());
+    }
+
     #[test]
-    fn simple_cursor_styled() {
-        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
-        let backend = TestBackend::new(80, 24);
+    fn letterbox_bars() {
+        let mut parser = vt100::Parser::new(2, 4, 0);
+        parser.process(b"ab\r\ncd");
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .cursor(Cursor::default().visibility(false))
+            .letterbox(Style::default().bg(Color::DarkGray));
+        let backend = TestBackend::new(8, 4);
         let mut terminal = Terminal::new(backend).unwrap();
-        let mut parser = vt100::Parser::new(24, 80, 0);
-        let style = Style::default().bg(Color::Cyan).fg(Color::LightRed);
-        let cursor = Cursor::default().symbol("|").style(style);
-        parser.process(stream);
-        let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(cursor);
         terminal
             .draw(|f| {
                 f.render_widget(pseudo_term, f.area());
@@ -393,15 +5044,16 @@ mod tests {
         let view = format!("{:?}", terminal.backend().buffer());
         insta::assert_snapshot!(view);
     }
+
     #[test]
-    fn simple_cursor_hide() {
-        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
-        let backend = TestBackend::new(80, 24);
+    fn extend_bg_to_width() {
+        let mut parser = vt100::Parser::new(2, 6, 0);
+        parser.process(b"\x1b[44mab\x1b[K\x1b[0m\r\ncd");
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .cursor(Cursor::default().visibility(false))
+            .extend_bg_to_width(true);
+        let backend = TestBackend::new(10, 2);
         let mut terminal = Terminal::new(backend).unwrap();
-        let mut parser = vt100::Parser::new(24, 80, 0);
-        let cursor = Cursor::default().visibility(false);
-        parser.process(stream);
-        let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(cursor);
         terminal
             .draw(|f| {
                 f.render_widget(pseudo_term, f.area());
@@ -410,16 +5062,16 @@ mod tests {
         let view = format!("{:?}", terminal.backend().buffer());
         insta::assert_snapshot!(view);
     }
+
     #[test]
-    fn simple_cursor_hide_alt() {
-        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
-        let backend = TestBackend::new(80, 24);
+    fn truncation_marker() {
+        let mut parser = vt100::Parser::new(4, 20, 0);
+        parser.process(b"short\r\nlonger than twelve\r\nexactly 12 c");
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .cursor(Cursor::default().visibility(false))
+            .truncation_marker(Some("…"));
+        let backend = TestBackend::new(12, 4);
         let mut terminal = Terminal::new(backend).unwrap();
-        let mut parser = vt100::Parser::new(24, 80, 0);
-        let mut cursor = Cursor::default();
-        cursor.hide();
-        parser.process(stream);
-        let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(cursor);
         terminal
             .draw(|f| {
                 f.render_widget(pseudo_term, f.area());
@@ -428,188 +5080,807 @@ mod tests {
         let view = format!("{:?}", terminal.backend().buffer());
         insta::assert_snapshot!(view);
     }
+
     #[test]
-    fn overlapping_cursor() {
-        let stream = include_bytes!("../test/typescript/overlapping_cursor.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn render_and_report_one_line_change() {
+        let mut parser = vt100::Parser::new(3, 10, 0);
+        parser.process(b"one\r\ntwo\r\n");
+        let mut state = PseudoTerminalState::new();
+        let area = Rect::new(0, 0, 10, 3);
+        let mut buf = Buffer::empty(area);
+        let damage =
+            PseudoTerminal::new(parser.screen()).render_and_report(&mut state, area, &mut buf);
+        assert_eq!(
+            damage,
+            [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1), (0, 2)]
+        );
+
+        parser.process(b"tw");
+        let damage =
+            PseudoTerminal::new(parser.screen()).render_and_report(&mut state, area, &mut buf);
+        assert_eq!(damage, [(0, 2), (1, 2), (2, 2)]);
     }
+
     #[test]
-    fn overlapping_cursor_alternate_style() {
-        let stream = include_bytes!("../test/typescript/overlapping_cursor.typescript");
-        let backend = TestBackend::new(80, 24);
-        let mut terminal = Terminal::new(backend).unwrap();
+    fn region_of_ls() {
+        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
         let mut parser = vt100::Parser::new(24, 80, 0);
-        let style = Style::default().bg(Color::Cyan).fg(Color::LightRed);
-        let cursor = Cursor::default().overlay_style(style);
         parser.process(stream);
-        let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(cursor);
-        terminal
-            .draw(|f| {
-                f.render_widget(pseudo_term, f.area());
-            })
-            .unwrap();
-        let view = format!("{:?}", terminal.backend().buffer());
-        insta::assert_snapshot!(view);
+        let pseudo_term = PseudoTerminal::new(parser.screen());
+
+        let region = pseudo_term.region(Rect::new(18, 0, 3, 3));
+        let text = region
+            .iter()
+            .map(|row| row.iter().map(CellInfo::symbol).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(text, [" do", " ex", " fl"]);
+        assert_eq!(region[0][1].fg, Color::Indexed(4));
+        assert_eq!(region[0][1].modifier, Modifier::BOLD);
+
+        let edge = pseudo_term.region(Rect::new(79, 23, 2, 2));
+        assert_eq!(edge.len(), 2);
+        assert!(edge.iter().all(|row| row.len() == 2));
+        assert_eq!(edge[1][1], CellInfo::default());
     }
+
     #[test]
-    fn simple_ls_with_block() {
-        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
-        let backend = TestBackend::new(100, 24);
-        let mut terminal = Terminal::new(backend).unwrap();
-        let mut parser = vt100::Parser::new(24, 80, 0);
-        parser.process(stream);
-        let block = Block::default().borders(Borders::ALL).title("ls");
-        let pseudo_term = PseudoTerminal::new(parser.screen()).block(block);
-        terminal
-            .draw(|f| {
-                f.render_widget(pseudo_term, f.area());
-            })
-            .unwrap();
-        let view = format!("{:?}", terminal.backend().buffer());
-        insta::assert_snapshot!(view);
+    fn frame_cursor_position_in_block() {
+        let mut parser = vt100::Parser::new(4, 10, 0);
+        parser.process(b"abc\r\nde");
+        let area = Rect::new(2, 3, 12, 6);
+        let pseudo_term = PseudoTerminal::new(parser.screen()).block(Block::bordered());
+        assert_eq!(
+            pseudo_term.frame_cursor_position(area),
+            Some(Position::new(5, 5))
+        );
+
+        let mut panned = PseudoTerminal::new(parser.screen());
+        panned.h_offset = 3;
+        assert_eq!(panned.frame_cursor_position(area), None);
+
+        let hidden =
+            PseudoTerminal::new(parser.screen()).cursor(Cursor::default().visibility(false));
+        assert_eq!(hidden.frame_cursor_position(area), None);
     }
+
     #[test]
-    fn simple_ls_no_style_from_block() {
-        let stream = include_bytes!("../test/typescript/simple_ls.typescript");
-        let backend = TestBackend::new(100, 24);
-        let mut terminal = Terminal::new(backend).unwrap();
-        let mut parser = vt100::Parser::new(24, 80, 0);
-        parser.process(stream);
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .style(Style::default().add_modifier(Modifier::BOLD))
-            .title("ls");
-        let pseudo_term = PseudoTerminal::new(parser.screen()).block(block);
-        terminal
-            .draw(|f| {
-                f.render_widget(pseudo_term, f.area());
+    fn frame_cursor_position_rotated_and_flipped() {
+        let mut parser = vt100::Parser::new(3, 4, 0);
+        parser.process(b"ab\r\nc");
+        let area = Rect::new(0, 0, 4, 4);
+        let configs = [
+            (Rotation::None, Some(FlipMode::Horizontal)),
+            (Rotation::None, Some(FlipMode::Vertical)),
+            (Rotation::Deg180, None),
+            (Rotation::Cw90, None),
+            (Rotation::Ccw90, Some(FlipMode::Both)),
+        ];
+        for (rotation, flip) in configs {
+            let pseudo_term = || {
+                let pseudo_term = PseudoTerminal::new(parser.screen()).rotate(rotation);
+                match flip {
+                    Some(flip) => pseudo_term.flip(flip),
+                    None => pseudo_term,
+                }
+            };
+            let buf = render_to_buffer(pseudo_term(), 4, 4);
+            let drawn = area.positions().find(|&p| buf[p].symbol() == "\u{2588}");
+            assert!(drawn.is_some());
+            assert_eq!(
+                pseudo_term().frame_cursor_position(area),
+                drawn,
+                "{rotation:?} {flip:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn palette_bright_slots() {
+        let mut parser = vt100::Parser::new(1, 4, 0);
+        // Red, bright red and a bright red background
+        parser.process(b"\x1b[31ma\x1b[91mb\x1b[39;101mc");
+        let palette = Palette::new().bright(1, Color::Rgb(255, 110, 110));
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .render_cursor(false)
+            .palette(palette);
+        let buf = render_to_buffer(pseudo_term, 4, 1);
+
+        assert_eq!(buf[(0, 0)].fg, Color::Indexed(1));
+        assert_eq!(buf[(1, 0)].fg, Color::Rgb(255, 110, 110));
+        assert_eq!(buf[(2, 0)].bg, Color::Rgb(255, 110, 110));
+        assert_eq!(palette.resolve(Color::LightRed), Color::Rgb(255, 110, 110));
+        assert_eq!(palette.normal(9, Color::Blue), palette);
+    }
+
+    #[test]
+    fn base_style_under_program_style() {
+        let mut parser = vt100::Parser::new(1, 4, 0);
+        parser.process(b"a\x1b[31;44mb\x1b[0m");
+        let base = Style::default()
+            .fg(Color::White)
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::ITALIC);
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .cursor(Cursor::default().visibility(false))
+            .base_style(base);
+        let buf = render_to_buffer(pseudo_term, 4, 1);
+
+        let unstyled = &buf[(0, 0)];
+        assert_eq!((unstyled.fg, unstyled.bg), (Color::White, Color::DarkGray));
+        assert_eq!(unstyled.modifier, Modifier::ITALIC);
+        let styled = &buf[(1, 0)];
+        assert_eq!(
+            (styled.fg, styled.bg),
+            (Color::Indexed(1), Color::Indexed(4))
+        );
+        assert_eq!(styled.modifier, Modifier::ITALIC);
+        assert_eq!(buf[(3, 0)].bg, Color::DarkGray);
+    }
+
+    #[test]
+    fn frozen_frame_ignores_screen_changes() {
+        let mut parser = vt100::Parser::new(2, 6, 0);
+        parser.process(b"before");
+        let mut state = PseudoTerminalState::new();
+        let area = Rect::new(0, 0, 6, 2);
+        let mut render = |parser: &vt100::Parser, frozen: bool| {
+            let mut buf = Buffer::empty(area);
+            ratatui::widgets::StatefulWidget::render(
+                PseudoTerminal::new(parser.screen()).frozen(frozen),
+                area,
+                &mut buf,
+                &mut state,
+            );
+            buf
+        };
+
+        let paused = render(&parser, true);
+        parser.process(b"\r\nafter");
+        assert_eq!(render(&parser, true), paused);
+        let live = render(&parser, false);
+        assert_ne!(live, paused);
+        assert_eq!(live[(0, 1)].symbol(), "a");
+    }
+
+    #[test]
+    fn cell_colors() {
+        let mut parser = vt100::Parser::new(1, 4, 0);
+        parser.process(b"\x1b[31mr\x1b[44mb");
+        let screen = parser.screen();
+        let red = Cell::fg(screen.cell(0, 0).unwrap());
+        // vt100 reports the 16 ANSI colors by their index
+        assert_eq!(red, Color::Indexed(1));
+        assert_eq!(palette::ansi_index(red), palette::ansi_index(Color::Red));
+        assert_eq!(Cell::bg(screen.cell(0, 0).unwrap()), Color::Reset);
+        assert_eq!(Cell::bg(screen.cell(0, 1).unwrap()), Color::Indexed(4));
+
+        let snapshot = ScreenSnapshot::new(screen);
+        assert_eq!(Cell::fg(snapshot.cell(0, 1).unwrap()), Color::Indexed(1));
+        assert_eq!(Cell::fg(snapshot.cell(0, 2).unwrap()), Color::Reset);
+    }
+
+    #[test]
+    fn scroll_percentage_label() {
+        assert_eq!(scroll_percentage(47, 100), "47%");
+        assert_eq!(scroll_percentage(1, 3), "33%");
+        assert_eq!(scroll_percentage(5, 0), "100%");
+
+        let mut parser = vt100::Parser::new(2, 6, 10);
+        parser.process(b"1\r\n2\r\n3\r\n4\r\n5");
+        let mut state = PseudoTerminalState::new();
+        parser.set_scrollback(usize::MAX);
+        state.set_scrollback_len(parser.screen().scrollback());
+        let area = Rect::new(0, 0, 6, 2);
+        let render = |parser: &vt100::Parser, state: &mut PseudoTerminalState| {
+            let mut buf = Buffer::empty(area);
+            let pseudo_term = PseudoTerminal::new(parser.screen())
+                .scroll_percentage_label(Some(Corner::TopRight));
+            ratatui::widgets::StatefulWidget::render(pseudo_term, area, &mut buf, state);
+            buf
+        };
+
+        parser.set_scrollback(1);
+        state.set_scroll_offset(parser.screen().scrollback());
+        let buf = render(&parser, &mut state);
+        let top: String = (0..6).map(|x| buf[(x, 0)].symbol()).collect();
+        assert_eq!(top, "3  33%");
+
+        // Hidden at the bottom
+        parser.set_scrollback(0);
+        state.set_scroll_offset(parser.screen().scrollback());
+        let buf = render(&parser, &mut state);
+        assert_eq!(buf[(5, 0)].symbol(), " ");
+    }
+
+    #[test]
+    fn static_render_strips_blink() {
+        let mut parser = vt100::Parser::new(1, 4, 0);
+        parser.process(b"ab\x1b[?25l");
+        let render = |parser: &vt100::Parser, static_render: bool| {
+            let pseudo_term = PseudoTerminal::new(parser.screen())
+                .base_style(Style::default().add_modifier(Modifier::SLOW_BLINK))
+                .cursor(Cursor::default().symbol("|").bar_width(2))
+                .static_render(static_render);
+            render_to_buffer(pseudo_term, 4, 1)
+        };
+
+        let buf = render(&parser, false);
+        assert!(buf[(0, 0)].modifier.contains(Modifier::SLOW_BLINK));
+        assert_eq!(buf[(2, 0)].symbol(), " ");
+
+        let buf = render(&parser, true);
+        assert!(!buf[(0, 0)].modifier.contains(Modifier::SLOW_BLINK));
+        assert_eq!(buf[(0, 0)].symbol(), "a");
+        assert_eq!(buf[(2, 0)].symbol(), "█");
+
+        // Over a filled cell the block replaces the contents, not only the style
+        let mut parser = vt100::Parser::new(1, 4, 0);
+        parser.process(b"ab\x1b[1;1H");
+        assert_eq!(render(&parser, false)[(0, 0)].symbol(), "a");
+        assert_eq!(render(&parser, true)[(0, 0)].symbol(), "█");
+    }
+
+    #[test]
+    fn render_budget_defers_cells() {
+        let mut parser = vt100::Parser::new(2, 12, 0);
+        parser.process(b"\x1b[?25lhello world");
+        let area = Rect::new(0, 0, 12, 2);
+        let full = render_to_buffer(PseudoTerminal::new(parser.screen()), 12, 2);
+        let mut state = PseudoTerminalState::new().render_budget(4);
+        let mut render = |parser: &vt100::Parser| {
+            let mut buf = Buffer::empty(area);
+            ratatui::widgets::StatefulWidget::render(
+                PseudoTerminal::new(parser.screen()),
+                area,
+                &mut buf,
+                &mut state,
+            );
+            buf
+        };
+        let written = |buf: &Buffer| {
+            let blank = Buffer::empty(area);
+            area.positions().filter(|&p| buf[p] != blank[p]).count()
+        };
+
+        assert_eq!(written(&render(&parser)), 4);
+        assert_eq!(written(&render(&parser)), 8);
+        assert_eq!(render(&parser), full);
+
+        // The row, that changed last, is written first
+        parser.process(b"\x1b[H\x1b[2KHELLO");
+        assert_eq!(render(&parser)[(3, 0)].symbol(), "L");
+        parser.process(b"\r\nabc");
+        let buf = render(&parser);
+        assert_eq!(buf[(2, 1)].symbol(), "c");
+        assert_eq!(buf[(4, 0)].symbol(), "O");
+        assert_eq!(buf[(6, 0)].symbol(), "w");
+        let full = render_to_buffer(PseudoTerminal::new(parser.screen()), 12, 2);
+        assert_ne!(render(&parser), full);
+        assert_eq!(render(&parser), full);
+    }
+
+    #[test]
+    fn braille_preview_cell_aspect() {
+        let mut parser = vt100::Parser::new(4, 2, 0);
+        parser.process(b"##\r\n\r\n\r\n##");
+        let area = Rect::new(0, 0, 10, 10);
+        let preview = PseudoTerminal::new(parser.screen()).braille_preview(area);
+        assert_eq!(preview.lines.len(), 1);
+
+        let preview = PseudoTerminal::new(parser.screen())
+            .cell_aspect(2.0)
+            .braille_preview(area);
+        let lines: Vec<String> = preview.lines.iter().map(ToString::to_string).collect();
+        // Every row covers two dots: the top row the first two, the bottom row the last two
+        assert_eq!(lines, ["\u{281b}", "\u{28e4}"]);
+
+        // The stretched rows of a tall screen exceed `u16::MAX`
+        let screen = MockScreen::new(&["#"; 7000]);
+        let preview = PseudoTerminal::new(&screen)
+            .cell_aspect(10.0)
+            .braille_preview(Rect::new(0, 0, 1, 1));
+        assert_eq!(preview.lines.len(), 1);
+    }
+
+    #[test]
+    fn handle_click_link_word_and_empty() {
+        let mut parser = vt100::Parser::new(3, 40, 0);
+        parser.process("cargo  https://docs.rs/tui-term\r\n漢字 test".as_bytes());
+        let pseudo_term = PseudoTerminal::new(parser.screen()).block(Block::bordered());
+        let area = Rect::new(10, 5, 42, 5);
+        let click = |x: u16, y: u16| pseudo_term.handle_click(area, Position::new(x, y));
+
+        assert_eq!(
+            click(11 + 12, 6),
+            Some(ClickAction::Link {
+                url: "https://docs.rs/tui-term".to_string(),
+                bounds: Rect::new(7, 0, 24, 1),
             })
-            .unwrap();
-        let view = format!("{:?}", terminal.backend().buffer());
-        insta::assert_snapshot!(view);
+        );
+        assert_eq!(
+            click(11 + 2, 6),
+            Some(ClickAction::Word {
+                text: "cargo".to_string(),
+                bounds: Rect::new(0, 0, 5, 1),
+            })
+        );
+        assert_eq!(
+            click(11 + 3, 7),
+            Some(ClickAction::Word {
+                text: "漢字".to_string(),
+                bounds: Rect::new(0, 1, 4, 1),
+            })
+        );
+        assert_eq!(
+            click(11 + 6, 6),
+            Some(ClickAction::Empty { row: 0, col: 6 })
+        );
+        assert_eq!(click(10, 6), None);
     }
+
     #[test]
-    fn italic_text() {
-        let stream = b"[3mThis line will be displayed in italic.[0m This should have no style.";
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn transparent_colors_over_buffer() {
+        let mut parser = vt100::Parser::new(1, 5, 0);
+        // Black and magenta are keyed out, by name and by index
+        parser.process(b"\x1b[40m \x1b[45;32mb\x1b[35;44mc\x1b[30;40md\x1b[0me");
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .render_cursor(false)
+            .transparent_colors(&[Color::Black, Color::Indexed(5)]);
+        let area = Rect::new(0, 0, 5, 1);
+        let mut buf = Buffer::empty(area);
+        buf.set_string(
+            0,
+            0,
+            "#####",
+            Style::default().fg(Color::Gray).bg(Color::White),
+        );
+        pseudo_term.render(area, &mut buf);
+
+        let symbols = (0..5).map(|x| buf[(x, 0)].symbol()).collect::<String>();
+        assert_eq!(symbols, "#bc#e");
+        assert_eq!(
+            (buf[(1, 0)].fg, buf[(1, 0)].bg),
+            (Color::Indexed(2), Color::White)
+        );
+        assert_eq!(
+            (buf[(2, 0)].fg, buf[(2, 0)].bg),
+            (Color::Gray, Color::Indexed(4))
+        );
+        assert_eq!(
+            (buf[(4, 0)].fg, buf[(4, 0)].bg),
+            (Color::Reset, Color::Reset)
+        );
     }
+
     #[test]
-    fn underlined_text() {
-        let stream =
-            b"[4mThis line will be displayed with an underline.[0m This should have no style.";
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn input_preview_at_cursor() {
+        let mut parser = vt100::Parser::new(2, 8, 0);
+        parser.process(b"$ ");
+        let cursor = Cursor::default().overlay_style(Style::default().bg(Color::Red));
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .cursor(cursor)
+            .input_preview(Some("lsblk"));
+        let buf = render_to_buffer(pseudo_term, 6, 2);
+
+        let row = (0..6).map(|x| buf[(x, 0)].symbol()).collect::<String>();
+        assert_eq!(row, "$ lsbl");
+        assert_eq!(buf[(2, 0)].bg, Color::Red);
+        assert!(buf[(3, 0)].modifier.contains(Modifier::DIM));
+        assert_eq!(buf[(3, 0)].bg, Color::Reset);
+        assert!(!buf[(1, 0)].modifier.contains(Modifier::DIM));
+        assert_eq!(parser.screen().cursor_position(), (0, 2));
     }
+
     #[test]
-    fn bold_text() {
-        let stream = b"[1mThis line will be displayed bold.[0m This should have no style.";
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn cell_contents() {
+        let mut parser = vt100::Parser::new(1, 4, 0);
+        parser.process("a字".as_bytes());
+        let screen = parser.screen();
+        let contents = |col: u16| Cell::contents(screen.cell(0, col).unwrap());
+        assert_eq!(contents(0), "a");
+        assert_eq!(contents(1), "字");
+        assert_eq!(contents(2), "");
+        assert_eq!(contents(3), "");
+
+        let snapshot = ScreenSnapshot::new(screen);
+        assert_eq!(snapshot.cell(0, 1).unwrap().contents(), "字");
+        let mock = MockCell {
+            contents: "x".into(),
+            ..MockCell::default()
+        };
+        assert_eq!(mock.contents(), "x");
     }
+
     #[test]
-    fn inverse_text() {
-        let stream = b"[7mThis line will be displayed inversed.[0m This should have no style.";
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn sub_row_offset_half() {
+        let mut parser = vt100::Parser::new(2, 3, 0);
+        parser.process(b"\x1b[32ma\x1b[44m \x1b[0m \r\nb");
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .render_cursor(false)
+            .sub_row_offset(0.5);
+        let buf = render_to_buffer(pseudo_term, 3, 2);
+
+        let glyphs = (0..3).map(|x| buf[(x, 0)].symbol()).collect::<Vec<_>>();
+        assert_eq!(glyphs, ["▄", "▄", " "]);
+        assert_eq!(buf[(0, 0)].fg, Color::Indexed(2));
+        assert_eq!(buf[(1, 0)].fg, Color::Indexed(4));
+        assert_eq!(buf[(1, 0)].bg, Color::Reset);
+        assert_eq!(buf[(0, 1)].symbol(), "b");
     }
+
     #[test]
-    fn combined_modifier_text() {
-        let stream =
-            b"[4m[3mThis line will be displayed in italic and underlined.[0m This should have no style.";
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn clear_style_fills_uncovered_cells() {
+        let mut parser = vt100::Parser::new(2, 3, 0);
+        parser.process(b"\r\n\x1b[41ma\x1b[0mb");
+        let style = Style::default().bg(Color::Black);
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .render_cursor(false)
+            .clear_style(style);
+        let area = Rect::new(0, 0, 5, 3);
+        let mut buf = Buffer::empty(area);
+        buf.set_string(0, 2, "stale", Style::default().add_modifier(Modifier::BOLD));
+        pseudo_term.render(area, &mut buf);
+
+        for (x, y) in [(3, 0), (4, 1), (0, 2), (4, 2)] {
+            assert_eq!(buf[(x, y)].symbol(), " ");
+            assert_eq!(buf[(x, y)].bg, Color::Black);
+            assert_eq!(buf[(x, y)].modifier, Modifier::empty());
+        }
+        // Blank cells of the screen are filled as well, colors set by the program are kept
+        assert_eq!(buf[(0, 0)].bg, Color::Black);
+        assert_eq!(buf[(0, 1)].bg, Color::Indexed(1));
+        assert_eq!(buf[(1, 1)].bg, Color::Black);
     }
 
     #[test]
-    fn vttest_02_01() {
-        let stream = include_bytes!("../test/typescript/vttest_02_01.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn row_spans_of_styled_row() {
+        let mut parser = vt100::Parser::new(2, 12, 0);
+        parser.process(b"$ \x1b[31merror\x1b[0m: \x1b[1;4mx\x1b[0m\r\n");
+        let pseudo_term = PseudoTerminal::new(parser.screen());
+
+        let spans = pseudo_term.row_spans(0);
+        let contents: Vec<_> = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(contents, ["$ ", "error", ": ", "x", "  "]);
+        assert_eq!(spans[1].style.fg, Some(Color::Indexed(1)));
+        assert_eq!(spans[2].style.fg, Some(Color::Reset));
+        assert_eq!(
+            spans[3].style.add_modifier,
+            Modifier::BOLD | Modifier::UNDERLINED
+        );
+        assert_eq!(pseudo_term.row_spans(1).len(), 1);
+        assert!(pseudo_term.row_spans(2).is_empty());
     }
+
     #[test]
-    fn vttest_02_02() {
-        let stream = include_bytes!("../test/typescript/vttest_02_02.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn highlight_layer_order() {
+        let mut parser = vt100::Parser::new(3, 4, 0);
+        parser.process(b"\x1b[2;2H");
+        let render = |layers: HighlightLayers| {
+            let pseudo_term = PseudoTerminal::new(parser.screen())
+                .render_cursor(false)
+                .cursor_line_style(Some(Style::default().bg(Color::Red)))
+                .selection((0, 0), (1, 3), Style::default().bg(Color::Blue))
+                .highlight_layers(layers);
+            render_to_buffer(pseudo_term, 4, 3)
+        };
+
+        let buf = render(HighlightLayers::default());
+        assert_eq!(buf[(0, 1)].bg, Color::Blue);
+        let buf = render(HighlightLayers::new([
+            HighlightLayer::CursorColumn,
+            HighlightLayer::Selection,
+            HighlightLayer::CursorLine,
+        ]));
+        assert_eq!(buf[(0, 1)].bg, Color::Red);
+        assert_eq!(buf[(0, 0)].bg, Color::Blue);
+
+        // Only the topmost occurrence of a repeated layer is kept
+        let layers = HighlightLayers::new([
+            HighlightLayer::Selection,
+            HighlightLayer::CursorLine,
+            HighlightLayer::Selection,
+        ]);
+        assert_eq!(
+            layers.order,
+            [
+                None,
+                Some(HighlightLayer::CursorLine),
+                Some(HighlightLayer::Selection)
+            ]
+        );
+        assert_eq!(render(layers), render(HighlightLayers::default()));
     }
+
     #[test]
-    fn vttest_02_03() {
-        let stream = include_bytes!("../test/typescript/vttest_02_03.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn cursor_below_selection_without_selection_layer() {
+        let mut parser = vt100::Parser::new(3, 4, 0);
+        parser.process(b"\x1b[2;2H");
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .cursor(Cursor::default().draw_order(CursorOrder::BelowSelection))
+            .cursor_line_style(Some(Style::default().bg(Color::Red)))
+            .highlight_layers(HighlightLayers::new([
+                HighlightLayer::CursorLine,
+                HighlightLayer::CursorLine,
+                HighlightLayer::CursorColumn,
+            ]));
+        let buf = render_to_buffer(pseudo_term, 4, 3);
+        assert_eq!(buf[(1, 1)].symbol(), "\u{2588}");
+        assert_eq!(buf[(0, 1)].bg, Color::Red);
     }
+
     #[test]
-    fn vttest_02_04() {
-        let stream = include_bytes!("../test/typescript/vttest_02_04.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn selection_and_cursor_order() {
+        let mut parser = vt100::Parser::new(2, 4, 0);
+        parser.process(b"abcd\r\nefgh\x1b[1;3H");
+        let selected = Style::default().bg(Color::Blue);
+        let render = |draw_order: CursorOrder| {
+            let cursor = Cursor::default()
+                .overlay_style(Style::default().bg(Color::Red))
+                .draw_order(draw_order);
+            let pseudo_term = PseudoTerminal::new(parser.screen())
+                .cursor(cursor)
+                .selection((1, 1), (0, 2), selected);
+            render_to_buffer(pseudo_term, 4, 2)
+        };
+
+        let buf = render(CursorOrder::AboveContent);
+        let bg = |y: u16| (0..4).map(|x| buf[(x, y)].bg).collect::<Vec<_>>();
+        assert_eq!(bg(0), [Color::Reset, Color::Reset, Color::Red, Color::Blue]);
+        assert_eq!(
+            bg(1),
+            [Color::Blue, Color::Blue, Color::Reset, Color::Reset]
+        );
+
+        let buf = render(CursorOrder::BelowSelection);
+        assert_eq!(buf[(2, 0)].bg, Color::Blue);
     }
+
     #[test]
-    fn vttest_02_05() {
-        let stream = include_bytes!("../test/typescript/vttest_02_05.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn cursor_placement() {
+        let mut parser = vt100::Parser::new(1, 4, 0);
+        parser.process(b"ab\x1b[1;2H");
+        let render = |parser: &vt100::Parser, placement: CursorPlacement| {
+            let cursor = Cursor::default().symbol("|").placement(placement);
+            let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(cursor);
+            render_to_buffer(pseudo_term, 4, 1)
+        };
+
+        // Over a filled cell
+        let buf = render(&parser, CursorPlacement::Auto);
+        assert_eq!(buf[(1, 0)].symbol(), "b");
+        assert!(buf[(1, 0)].modifier.contains(Modifier::REVERSED));
+        let buf = render(&parser, CursorPlacement::Symbol);
+        assert_eq!(buf[(1, 0)].symbol(), "|");
+        assert_eq!(buf[(1, 0)].fg, Color::Gray);
+
+        // Over an empty cell
+        parser.process(b"\x1b[1;4H");
+        let buf = render(&parser, CursorPlacement::Auto);
+        assert_eq!(buf[(3, 0)].symbol(), "|");
+        let buf = render(&parser, CursorPlacement::Overlay);
+        assert_eq!(buf[(3, 0)].symbol(), " ");
+        assert!(buf[(3, 0)].modifier.contains(Modifier::REVERSED));
     }
+
     #[test]
-    fn vttest_02_06() {
-        let stream = include_bytes!("../test/typescript/vttest_02_06.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn cursor_position_override() {
+        let mut parser = vt100::Parser::new(3, 5, 0);
+        parser.process(b"ab");
+        let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(
+            Cursor::default()
+                .symbol("_")
+                .position_override(Some((1, 3))),
+        );
+        let buf = render_to_buffer(pseudo_term, 5, 3);
+        assert_eq!(buf[(2, 0)].symbol(), " ");
+        assert_eq!(buf[(3, 1)].symbol(), "_");
+
+        // The cursor line and column follow the override
+        let highlight = Style::default().bg(Color::DarkGray);
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .cursor(Cursor::default().position_override(Some((1, 3))))
+            .cursor_line_style(Some(highlight))
+            .cursor_column_style(Some(highlight));
+        let buf = render_to_buffer(pseudo_term, 5, 3);
+        assert_eq!(buf[(0, 1)].bg, Color::DarkGray);
+        assert_eq!(buf[(0, 0)].bg, Color::Reset);
+        assert_eq!(buf[(3, 2)].bg, Color::DarkGray);
+        assert_eq!(buf[(2, 2)].bg, Color::Reset);
     }
+
     #[test]
-    fn vttest_02_07() {
-        let stream = include_bytes!("../test/typescript/vttest_02_07.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn show_cursor_coords() {
+        let mut parser = vt100::Parser::new(3, 12, 0);
+        parser.process(b"hello\r\nab");
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .cursor(Cursor::default().visibility(false))
+            .show_cursor_coords(Some(Corner::BottomRight));
+        let buf = render_to_buffer(pseudo_term, 12, 3);
+        let row = |y: u16| (0..12).map(|x| buf[(x, y)].symbol()).collect::<String>();
+        assert_eq!(row(2), "      (1, 2)");
+
+        let pseudo_term = PseudoTerminal::new(parser.screen()).show_cursor_coords(None);
+        assert!(pseudo_term.corner_statuses.is_empty());
     }
+
     #[test]
-    fn vttest_02_08() {
-        let stream = include_bytes!("../test/typescript/vttest_02_08.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn coordinates_in_origin_mode() {
+        let mut screen = MockScreen::new(&["row0", "row1", "row2", "row3", "row4"]);
+        screen.scroll_region = Some((1, 3));
+        let area = Rect::new(2, 1, 6, 7);
+        let block = Block::default().borders(Borders::ALL);
+        let mut state = PseudoTerminalState::new();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 10));
+        ratatui::widgets::StatefulWidget::render(
+            PseudoTerminal::new(&screen).block(block.clone()),
+            area,
+            &mut buf,
+            &mut state,
+        );
+        // The inner area starts at (3, 2)
+        {
+            let pseudo_term = PseudoTerminal::new(&screen).block(block.clone());
+            assert_eq!(pseudo_term.screen_coords(&state, 4, 5), Some((2, 2)));
+            assert_eq!(pseudo_term.screen_coords(&state, 1, 5), None);
+        }
+
+        screen.origin_mode = true;
+        let pseudo_term = PseudoTerminal::new(&screen).block(block);
+        assert_eq!(pseudo_term.screen_coords(&state, 3, 3), Some((0, 0)));
+        assert_eq!(pseudo_term.screen_coords(&state, 4, 5), Some((1, 2)));
+        assert_eq!(pseudo_term.screen_coords(&state, 5, 4), Some((2, 1)));
+        // Outside of the scroll region
+        assert_eq!(pseudo_term.screen_coords(&state, 2, 3), None);
+        assert_eq!(pseudo_term.screen_coords(&state, 6, 3), None);
+        // The cell doesn't depend on origin mode
+        assert_eq!(pseudo_term.cell_at(&state, 4, 5).unwrap().contents, "w");
+        assert_eq!(pseudo_term.cell_at(&state, 2, 3).unwrap().contents, "r");
     }
+
     #[test]
-    fn vttest_02_09() {
-        let stream = include_bytes!("../test/typescript/vttest_02_09.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn table_from_aligned_output() {
+        let mut parser = vt100::Parser::new(5, 30, 0);
+        parser.process(b"-rw-r--r--  1024 a.rs\r\n");
+        parser.process(b"drwxr-xr-x    96 src\r\n");
+        parser.process(b"-rw-r--r--     7 long name");
+        // Unordered, with a separator outside of the screen
+        let table = PseudoTerminal::new(parser.screen()).to_table(&[17, 10, 40]);
+        let expected = Table::new(
+            [
+                Row::new(["-rw-r--r--", "1024", "a.rs"]),
+                Row::new(["drwxr-xr-x", "96", "src"]),
+                Row::new(["-rw-r--r--", "7", "long name"]),
+            ],
+            [
+                Constraint::Length(10),
+                Constraint::Length(7),
+                Constraint::Length(13),
+            ],
+        );
+        assert_eq!(table, expected);
     }
+
     #[test]
-    fn vttest_02_10() {
-        let stream = include_bytes!("../test/typescript/vttest_02_10.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn half_opacity_blends_rgb() {
+        let mut parser = vt100::Parser::new(1, 4, 0);
+        parser.process(b"\x1b[38;2;200;100;0;48;5;21ma\x1b[0mb");
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .render_cursor(false)
+            .style(Style::default().bg(Color::Rgb(100, 100, 100)))
+            .opacity(0.5);
+        let buf = render_to_buffer(pseudo_term, 4, 1);
+        assert_eq!(buf[(0, 0)].fg, Color::Rgb(150, 100, 50));
+        // Indexed 21 resolves to (0, 0, 255)
+        assert_eq!(buf[(0, 0)].bg, Color::Rgb(50, 50, 178));
+        assert_eq!(buf[(1, 0)].fg, Color::Reset);
+        assert_eq!(buf[(1, 0)].bg, Color::Reset);
     }
+
     #[test]
-    fn vttest_02_11() {
-        let stream = include_bytes!("../test/typescript/vttest_02_11.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn bar_cursor_glyphs() {
+        let parser = vt100::Parser::new(1, 2, 0);
+        let glyph = |cursor: Cursor| {
+            let pseudo_term = PseudoTerminal::new(parser.screen()).cursor(cursor);
+            render_to_buffer(pseudo_term, 2, 1)[(0, 0)]
+                .symbol()
+                .to_string()
+        };
+        assert_eq!(glyph(Cursor::default().symbol("|")), "|");
+        assert_eq!(glyph(Cursor::default().bar_width(1)), "▏");
+        assert_eq!(glyph(Cursor::default().bar_width(2)), "▎");
+        assert_eq!(glyph(Cursor::default().bar_width(4)), "▌");
+        assert_eq!(glyph(Cursor::default().bar_width(0)), "▏");
+        assert_eq!(glyph(Cursor::default().bar_width(20)), "█");
     }
+
     #[test]
-    fn vttest_02_12() {
-        let stream = include_bytes!("../test/typescript/vttest_02_12.typescript");
-        let view = snapshot_typescript(stream);
+    fn rotated_cw90() {
+        let mut parser = vt100::Parser::new(2, 3, 0);
+        parser.process(b"abc\r\nde");
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .cursor(Cursor::default().symbol("_"))
+            .rotate(Rotation::Cw90);
+        let buf = render_to_buffer(pseudo_term, 2, 3);
+        let view: Vec<String> = (0..3)
+            .map(|y| (0..2).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        let view = view.join("\n");
         insta::assert_snapshot!(view);
     }
+
     #[test]
-    fn vttest_02_13() {
-        let stream = include_bytes!("../test/typescript/vttest_02_13.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn flipped_rendering() {
+        let mut parser = vt100::Parser::new(2, 3, 0);
+        parser.process(b"abc\r\nde");
+        let lines = |flip: FlipMode| -> Vec<String> {
+            let pseudo_term = PseudoTerminal::new(parser.screen()).flip(flip);
+            let buf = render_to_buffer(pseudo_term, 3, 2);
+            (0..2)
+                .map(|y| (0..3).map(|x| buf[(x, y)].symbol()).collect())
+                .collect()
+        };
+        assert_eq!(lines(FlipMode::Horizontal), ["cba", "█ed"]);
+        assert_eq!(lines(FlipMode::Vertical), ["de█", "abc"]);
+        assert_eq!(lines(FlipMode::Both), ["█ed", "cba"]);
     }
+
     #[test]
-    fn vttest_02_14() {
-        let stream = include_bytes!("../test/typescript/vttest_02_14.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn insert_mode_flag() {
+        // vt100 doesn't track insert mode
+        let mut parser = vt100::Parser::new(2, 10, 0);
+        parser.process(b"\x1b[4h");
+        assert!(!parser.screen().insert_mode());
+        parser.process(b"\x1b[4l");
+        assert!(!parser.screen().insert_mode());
+
+        let mut screen = MockScreen::new(&[""]);
+        screen.insert_mode = true;
+        let dyn_screen: &dyn DynScreen = &screen;
+        assert!(dyn_screen.insert_mode());
+        assert!(ScreenSnapshot::new(&screen).insert_mode());
     }
+
     #[test]
-    fn vttest_02_15() {
-        let stream = include_bytes!("../test/typescript/vttest_02_15.typescript");
-        let view = snapshot_typescript(stream);
-        insta::assert_snapshot!(view);
+    fn application_keypad_flag() {
+        let mut parser = vt100::Parser::new(2, 10, 0);
+        assert!(!Screen::application_keypad(parser.screen()));
+        parser.process(b"\x1b=");
+        assert!(Screen::application_keypad(parser.screen()));
+        let dyn_screen: &dyn DynScreen = parser.screen();
+        assert!(dyn_screen.application_keypad());
+        assert!(ScreenSnapshot::new(parser.screen()).application_keypad());
+        parser.process(b"\x1b>");
+        assert!(!Screen::application_keypad(parser.screen()));
+
+        // The cursor key mode is independent of the keypad
+        assert!(!Screen::application_cursor(parser.screen()));
+        parser.process(b"\x1b[?1h");
+        assert!(Screen::application_cursor(parser.screen()));
+        assert!(!Screen::application_keypad(parser.screen()));
+        let dyn_screen: &dyn DynScreen = parser.screen();
+        assert!(dyn_screen.application_cursor());
+        assert!(ScreenSnapshot::new(parser.screen()).application_cursor());
+    }
+
+    #[test]
+    fn max_cols_caps_columns() {
+        let mut parser = vt100::Parser::new(2, 80, 0);
+        parser.process("x".repeat(80).as_bytes());
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .cursor(Cursor::default().visibility(false))
+            .max_cols(40);
+        let buf = render_to_buffer(pseudo_term, 80, 2);
+        assert!((0..40).all(|x| buf[(x, 0)].symbol() == "x"));
+        assert!((40..80).all(|x| buf[(x, 0)].symbol() == " "));
+
+        // The block still spans the whole area
+        let pseudo_term = PseudoTerminal::new(parser.screen())
+            .block(Block::default().borders(Borders::ALL))
+            .cursor(Cursor::default().visibility(false))
+            .max_cols(40);
+        let buf = render_to_buffer(pseudo_term, 80, 4);
+        assert_eq!(buf[(40, 1)].symbol(), "x");
+        assert_eq!(buf[(41, 1)].symbol(), " ");
+        assert_eq!(buf[(79, 1)].symbol(), "│");
     }
 }